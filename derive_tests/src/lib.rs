@@ -0,0 +1,633 @@
+//! Real models deriving every macro, exercised by the tests under `tests/`.
+//!
+//! The runtime crates generated code names are stubbed under `stubs/`.
+
+#![allow(dead_code)]
+pub mod status {
+    use derives::Enums;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Enums)]
+    pub enum Status {
+        #[default]
+        Active,
+        Inactive,
+    }
+}
+pub use status::Status;
+
+pub mod permission {
+    use derives::Enums;
+
+    #[derive(Debug, Clone, Default, PartialEq, Enums)]
+    #[enums(flags)]
+    pub enum Permission {
+        #[default]
+        #[enums(bit = 0)]
+        Read,
+        #[enums(bit = 1)]
+        Write,
+        #[enums(bit = 2)]
+        Admin,
+    }
+}
+pub use permission::{Permission, PermissionSet};
+
+pub mod profile {
+    use derives::Jsonb;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Jsonb)]
+    pub struct Profile {
+        pub bio: String,
+        pub links: Vec<String>,
+    }
+}
+pub use profile::Profile;
+
+pub mod paging {
+    use derives::Pagination;
+
+    #[derive(Debug, Clone, Default, PartialEq, Pagination)]
+    #[pagination(per_page = 10, max_per_page = 50, sorts = "created_at, email", order = "desc")]
+    pub struct ListParams {
+        pub page: i64,
+        pub per_page: i64,
+        pub sort: String,
+        pub order: String,
+    }
+}
+
+pub mod address {
+    use derives::IsEmpty;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, IsEmpty)]
+    pub struct Address {
+        pub city: String,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct Contact {
+        pub name: String,
+        #[serde(skip_serializing_if = "Address::is_empty_ref")]
+        pub address: Address,
+    }
+}
+
+pub mod settings {
+    use derives::Jsonb;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Jsonb)]
+    #[jsonb(max_bytes = 32, on_overflow = "truncate_fields")]
+    pub struct Settings {
+        pub theme: String,
+        pub notes: Vec<String>,
+    }
+}
+
+pub mod limits {
+    use derives::Jsonb;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Jsonb)]
+    #[jsonb(max_bytes = 16)]
+    pub struct Limits {
+        pub label: String,
+    }
+}
+
+pub mod user {
+    use derives::PostgreSQL;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    #[table(alias = "author, editor", id = "uuid_v7", driver = "any", notify, healthcheck, sqlcommenter, timeout_ms = 500, retries = 2, retention(column = "created_at", days = 90), projection(name = "UserSummary", fields = "id, email, age"))]
+    pub struct User {
+        #[column]
+        pub id: Null<String>,
+        #[column(unique = "email_taken")]
+        pub email: Null<String>,
+        #[column]
+        pub tags: Null<Vec<String>>,
+        #[column(check = "age_invalid")]
+        pub age: Null<i32>,
+        #[column(skip_compare)]
+        pub nickname: Null<String>,
+        #[column(created_at)]
+        pub created_at: Null<chrono::DateTime<chrono::Utc>>,
+        #[column(updated_at)]
+        pub updated_at: Null<chrono::DateTime<chrono::Utc>>,
+        #[column(skip_update, private)]
+        pub tenant_id: Null<String>,
+        #[column(readonly)]
+        pub created_by: Null<String>,
+        #[column(tsvector, language = "simple")]
+        pub tsv: Null<String>,
+        #[column(jsonb)]
+        pub profile: Null<crate::Profile>,
+        #[column(enum)]
+        pub status: Null<crate::Status>,
+        #[column(version)]
+        pub version: Null<i32>,
+    }
+}
+pub use user::User;
+
+pub mod post {
+    use derives::PostgreSQL;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    use crate::User;
+
+    pub fn next_id() -> String {
+        "post-1".to_string()
+    }
+
+    thread_local! {
+        pub static OBSERVED: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    pub struct Metrics;
+
+    impl derives::QueryObserver for Metrics {
+        fn observe(table: &'static str, operation: &'static str, _elapsed: std::time::Duration) {
+            OBSERVED.with(|o| o.borrow_mut().push(format!("{}:{}", table, operation)));
+        }
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    #[table(id = "crate::post::next_id", driver = "any", tenant = "user_id", metrics = "crate::post::Metrics", responder = "crate::errors", nulls = "crate::libs::nulls")]
+    pub struct Post {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub user_id: Null<String>,
+        #[column(getters = "pub(crate)", no_setter)]
+        pub password_hash: Null<String>,
+        #[relation(belongs_to = "User")]
+        pub user: Null<User>,
+        #[relation(has_many = "User", fk = "post_id")]
+        pub readers: Null<Vec<User>>,
+    }
+
+    // Crate-visible getters are reachable from inside the crate only
+    pub fn password_hash(post: &Post) -> Option<&String> {
+        post.password_hash_ref()
+    }
+}
+pub use post::Post;
+
+pub mod page {
+    use derives::PostgreSQL;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    pub trait Record:
+        Clone + Default + PartialEq + Serialize + serde::de::DeserializeOwned + Send + Sync + Unpin + 'static
+        + sqlx::Type<sqlx::Postgres> + sqlx::postgres::PgHasArrayType
+        + for<'q> sqlx::Encode<'q, sqlx::Postgres> + for<'r> sqlx::Decode<'r, sqlx::Postgres>
+    {
+    }
+
+    impl Record for String {}
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    #[table(rename = "pages")]
+    #[serde(bound(deserialize = "T: Record"))]
+    pub struct Page<T: Record> {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub records: Null<Vec<T>>,
+        #[column]
+        #[serde(rename = "pageTitle")]
+        pub title: Null<String>,
+        #[column(rename = "caption")]
+        #[serde(rename = "label")]
+        pub caption_text: Null<String>,
+        #[column(version, rename = "revision")]
+        pub version: Null<i32>,
+    }
+}
+
+pub mod draft {
+    use derives::PostgreSQL;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    pub struct Draft {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub title: Null<String>,
+        #[column]
+        pub body: Null<String>,
+        #[column(updated_at)]
+        pub updated_at: Null<chrono::DateTime<chrono::Utc>>,
+        #[serde(skip)]
+        pub touched: derives::Touched,
+    }
+}
+
+pub mod account {
+    use derives::MySQL;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, MySQL)]
+    #[table(rename = "accounts")]
+    pub struct Account {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub name: nulls::Null<std::string::String>,
+        #[column(readonly, private)]
+        pub created_by: Null<String>,
+        #[column(created_at)]
+        pub created_at: Null<chrono::DateTime<chrono::Utc>>,
+        #[column(updated_at)]
+        pub updated_at: Null<chrono::DateTime<chrono::Utc>>,
+        #[column(ignore)]
+        pub selected: Null<bool>,
+    }
+}
+pub use account::Account;
+
+pub mod search {
+    use derives::prelude::*;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    pub type Message = Null<String>;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Form)]
+    #[reference(filter = crate::user::query::Builder)]
+    pub struct UserSearchForm {
+        #[form(sanitize = "lowercase", filter = "eq", example = "jane@doe.com")]
+        pub email: Null<String>,
+        #[form(filter = "gt", example = "30", error = Message, error_message = "Age must be positive")]
+        pub age: Null<i32>,
+        #[form(filter = "overlaps")]
+        pub tags: Null<Vec<String>>,
+        pub page: Null<i64>,
+    }
+}
+
+pub mod secrets {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    thread_local! {
+        pub static AUDIT: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    pub fn record(node: &str, fields: &[&str], context: &str) {
+        AUDIT.with(|a| a.borrow_mut().push(format!("{}:{}:{}", node, fields.join(","), context)));
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(audit = crate::secrets::record)]
+    pub struct Secrets {
+        #[encryption(error_message = "API key is required")]
+        pub api_key: Null<String>,
+        #[encryption(fpe, mask = "last4")]
+        pub card_number: Null<String>,
+        #[encryption(deterministic, mask = "email")]
+        pub email: Null<String>,
+        pub label: Null<String>,
+    }
+}
+pub use secrets::Secrets;
+
+pub mod vault {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    pub struct Reverse;
+
+    impl Reverse {
+        pub fn encrypt(value: &Null<String>) -> Null<String> {
+            match value {
+                Null::Value(v) => Null::Value(v.chars().rev().collect()),
+                _ => value.clone(),
+            }
+        }
+
+        pub fn decrypt(value: &Null<String>) -> Null<String> {
+            match value {
+                Null::Value(v) if v == "corrupt" => Null::Null,
+                _ => Self::encrypt(value),
+            }
+        }
+
+        pub fn encrypt_with_key(value: &Null<String>, key: &str) -> Null<String> {
+            match Self::encrypt(value) {
+                Null::Value(v) => Null::Value(format!("{}:{}", key, v)),
+                value => value,
+            }
+        }
+
+        pub fn decrypt_with_key(value: &Null<String>, key: &str) -> Null<String> {
+            match value {
+                Null::Value(v) => Self::decrypt(&Null::Value(v.strip_prefix(&format!("{}:", key)).unwrap_or(v).to_string())),
+                _ => value.clone(),
+            }
+        }
+
+        pub fn sign(payload: &str) -> String {
+            format!("{:x}", payload.bytes().enumerate().map(|(i, b)| (i as u64 + 1) * b as u64).sum::<u64>())
+        }
+
+        pub fn verify(payload: &str, signature: &str) -> bool {
+            Self::sign(payload) == signature
+        }
+
+        pub async fn encrypt_async(value: &Null<String>) -> Result<Null<String>, String> {
+            match value {
+                Null::Value(v) if v == "unreachable" => Err("kms unavailable".to_string()),
+                _ => Ok(Self::encrypt(value)),
+            }
+        }
+
+        pub async fn decrypt_async(value: &Null<String>) -> Result<Null<String>, String> {
+            Ok(Self::decrypt(value))
+        }
+
+        pub async fn encrypt_with_key_async(value: &Null<String>, key: &str) -> Result<Null<String>, String> {
+            Ok(Self::encrypt_with_key(value, key))
+        }
+
+        pub async fn decrypt_with_key_async(value: &Null<String>, key: &str) -> Result<Null<String>, String> {
+            Ok(Self::decrypt_with_key(value, key))
+        }
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(cipher = "crate::vault::Reverse", async)]
+    pub struct Vault {
+        #[encryption]
+        pub api_key: Null<String>,
+        #[encryption(key = "payment")]
+        pub card_number: Null<String>,
+        pub label: Null<String>,
+    }
+}
+
+pub mod wallet {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    use crate::vault::Vault;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(cipher = "crate::vault::Reverse", async)]
+    pub struct Wallet {
+        #[encryption(nested)]
+        pub primary: Null<Vault>,
+        #[encryption(nested)]
+        pub backups: Null<Vec<Vault>>,
+        pub label: Null<String>,
+    }
+}
+
+pub mod envelope {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    pub struct Upper;
+
+    impl Upper {
+        pub fn decrypt(value: &Null<String>) -> Null<String> {
+            match value {
+                Null::Value(v) => Null::Value(v.to_lowercase()),
+                _ => value.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(cipher = "crate::vault::Reverse", version = "v2", legacy = "v1 = crate::envelope::Upper")]
+    pub struct Envelope {
+        #[encryption]
+        pub api_key: Null<String>,
+        pub label: Null<String>,
+    }
+}
+
+pub mod signed {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(cipher = "crate::vault::Reverse", integrity)]
+    pub struct Signed {
+        #[encryption]
+        pub api_key: Null<String>,
+        #[encryption(hash = "argon2")]
+        pub card_number: Null<String>,
+        #[encryption(nested)]
+        pub credentials: Null<crate::credentials::Credentials>,
+        pub signature: Null<String>,
+        pub label: Null<String>,
+    }
+}
+
+pub mod sealed {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    #[encryption(zeroize)]
+    pub struct Sealed {
+        #[encryption]
+        pub api_key: Null<String>,
+        pub label: Null<String>,
+    }
+}
+
+pub mod credentials {
+    use derives::Encryption;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encryption)]
+    pub struct Credentials {
+        #[encryption(hash = "argon2")]
+        pub api_key: Null<String>,
+        #[encryption]
+        pub label: Null<String>,
+    }
+}
+
+pub fn clone() -> Secrets {
+    Secrets::default()
+}
+
+pub mod models {
+    pub mod tags {
+        use derives::PostgreSQL;
+        use nulls::Null;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+        #[table(alias = "parent")]
+        pub struct Tag {
+            #[column]
+            pub id: Null<String>,
+            #[column]
+            pub name: Null<String>,
+            #[column(computed = "lower(name)")]
+            pub slug: Null<String>,
+            #[column(ignore)]
+            pub usage: Null<i64>,
+            #[column(parse_with = "crate::models::tags::parse_rank")]
+            pub rank: Null<i64>,
+        }
+
+        // Ranks are stored as TEXT
+        pub fn parse_rank(value: sqlx::postgres::PgValueRef<'_>) -> Result<i64, sqlx::error::BoxDynError> {
+            Ok(<&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?.parse()?)
+        }
+    }
+}
+
+pub mod legacy {
+    use derives::PostgreSQL;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    #[table(rename = "legacy", returning = "id, created_at")]
+    pub struct Legacy {
+        #[column]
+        pub id: Option<String>,
+        #[column]
+        pub name: Option<String>,
+        #[column]
+        pub labels: Option<Vec<String>>,
+        #[column]
+        pub score: nulls::Null<i32>,
+        #[column(created_at)]
+        pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+}
+pub use legacy::Legacy;
+
+pub mod audit_base {
+    use derives::PostgreSQL;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+    #[table(base)]
+    pub struct BaseAudit {
+        #[column]
+        pub id: nulls::Null<String>,
+        #[column(created_at)]
+        pub created_at: nulls::Null<chrono::DateTime<chrono::Utc>>,
+        #[column(updated_at)]
+        pub updated_at: nulls::Null<chrono::DateTime<chrono::Utc>>,
+    }
+}
+
+pub mod invoice {
+    use derives::PostgreSQL;
+    use serde::{Deserialize, Serialize};
+
+    crate::audit_base::base_audit! {
+        #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, PostgreSQL)]
+        #[table(rename = "invoices", extends = "crate::audit_base::BaseAudit")]
+        pub struct Invoice {
+            #[column]
+            pub total: nulls::Null<i64>,
+        }
+    }
+}
+
+pub mod libs {
+    pub use nulls;
+}
+
+pub mod errors {
+    pub use responder::{query, to, Error, Result};
+}
+
+pub mod note {
+    use derives::Sqlite;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Sqlite)]
+    #[table(responder = "crate::errors", nulls = "crate::libs::nulls")]
+    pub struct Note {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub body: Null<String>,
+        #[column(updated_at)]
+        pub updated_at: Null<chrono::DateTime<chrono::Utc>>,
+    }
+}
+pub use note::Note;
+
+pub mod document {
+    use derives::Model;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Model)]
+    #[model(rename = "documents", alias = "doc", jsonb, skip_generate = "responder, update, insert, query, stats", cache = "title")]
+    pub struct Document {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        pub title: Null<String>,
+    }
+}
+pub use document::Document;
+
+pub mod member {
+    use derives::Model;
+    use nulls::Null;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Model)]
+    #[model(rename = "members", encryption, is_empty, jsonb, max_bytes = 4096, skip_generate = "stats")]
+    #[serde(default)]
+    pub struct Member {
+        #[column]
+        pub id: Null<String>,
+        #[column]
+        #[encryption(deterministic)]
+        pub email: Null<String>,
+        #[column]
+        pub label: Null<String>,
+        #[serde(skip)]
+        pub touched: derives::Touched,
+    }
+}
+pub use member::Member;
+
+pub mod ledger {
+    use derives::Model;
+    use serde::{Deserialize, Serialize};
+
+    // Composed generators reach Null and the responder through the configured paths only
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Model)]
+    #[model(rename = "ledgers", responder = "crate::errors", nulls = "crate::libs::nulls", encryption, is_empty, jsonb)]
+    pub struct Ledger {
+        #[column]
+        pub id: crate::libs::nulls::Null<String>,
+        #[column]
+        #[encryption(mask = "last4", sanitize = "trim")]
+        pub label: crate::libs::nulls::Null<String>,
+    }
+}
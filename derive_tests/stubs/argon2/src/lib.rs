@@ -0,0 +1,81 @@
+//! Minimal stand-in for the `argon2` crate generated code calls into.
+
+#[derive(Default)]
+pub struct Argon2;
+
+pub mod password_hash {
+    pub mod rand_core {
+        pub struct OsRng;
+    }
+
+    #[derive(Debug)]
+    pub struct Error;
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "password hash error")
+        }
+    }
+
+    pub struct SaltString(String);
+    impl SaltString {
+        pub fn generate(_: &mut rand_core::OsRng) -> Self {
+            SaltString("salt".into())
+        }
+    }
+
+    pub struct PasswordHash<'a> {
+        salt: &'a str,
+        digest: String,
+    }
+    impl<'a> PasswordHash<'a> {
+        pub fn new(s: &'a str) -> Result<Self, Error> {
+            let rest = s.strip_prefix("$argon2$").ok_or(Error)?;
+            let (salt, digest) = rest.split_once('$').ok_or(Error)?;
+            Ok(PasswordHash {
+                salt,
+                digest: digest.to_string(),
+            })
+        }
+    }
+    impl std::fmt::Display for PasswordHash<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "$argon2${}${}", self.salt, self.digest)
+        }
+    }
+
+    fn digest(pw: &[u8]) -> String {
+        pw.iter().rev().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub trait PasswordHasher {
+        fn hash_password<'a>(
+            &self,
+            pw: &[u8],
+            salt: &'a SaltString,
+        ) -> Result<PasswordHash<'a>, Error>;
+    }
+    impl PasswordHasher for super::Argon2 {
+        fn hash_password<'a>(
+            &self,
+            pw: &[u8],
+            salt: &'a SaltString,
+        ) -> Result<PasswordHash<'a>, Error> {
+            Ok(PasswordHash {
+                salt: &salt.0,
+                digest: digest(pw),
+            })
+        }
+    }
+    pub trait PasswordVerifier {
+        fn verify_password(&self, pw: &[u8], hash: &PasswordHash<'_>) -> Result<(), Error>;
+    }
+    impl PasswordVerifier for super::Argon2 {
+        fn verify_password(&self, pw: &[u8], hash: &PasswordHash<'_>) -> Result<(), Error> {
+            if digest(pw) == hash.digest {
+                Ok(())
+            } else {
+                Err(Error)
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Minimal stand-in for the `ciphers` crate generated code calls into.
+
+pub trait CipherExt {
+    fn encrypt(&self) -> Self;
+    fn decrypt(&self) -> Self;
+}
+impl<T: Clone> CipherExt for T {
+    fn encrypt(&self) -> Self {
+        self.clone()
+    }
+    fn decrypt(&self) -> Self {
+        self.clone()
+    }
+}
+pub trait FpeExt {
+    fn encrypt_fpe(&self) -> Self;
+    fn decrypt_fpe(&self) -> Self;
+}
+impl<T: Clone> FpeExt for T {
+    fn encrypt_fpe(&self) -> Self {
+        self.clone()
+    }
+    fn decrypt_fpe(&self) -> Self {
+        self.clone()
+    }
+}
+pub trait DeterministicExt {
+    fn encrypt_deterministic(&self) -> Self;
+    fn decrypt_deterministic(&self) -> Self;
+}
+impl<T: Clone> DeterministicExt for T {
+    fn encrypt_deterministic(&self) -> Self {
+        self.clone()
+    }
+    fn decrypt_deterministic(&self) -> Self {
+        self.clone()
+    }
+}
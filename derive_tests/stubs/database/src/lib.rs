@@ -0,0 +1,8 @@
+//! Minimal stand-in for the `database` crate generated code calls into.
+
+pub fn writer() -> &'static sqlx::PgPool {
+    unimplemented!()
+}
+pub fn reader() -> &'static sqlx::PgPool {
+    unimplemented!()
+}
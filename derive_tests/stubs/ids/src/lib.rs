@@ -0,0 +1,14 @@
+//! Minimal stand-in for the `ids` crate generated code calls into.
+
+pub fn sm() -> String {
+    "sm".into()
+}
+pub fn md() -> String {
+    "md".into()
+}
+pub fn lg() -> String {
+    "lg".into()
+}
+pub fn max() -> String {
+    "max".into()
+}
@@ -0,0 +1,85 @@
+//! Minimal stand-in for the `nulls` crate generated code calls into.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Null<T> {
+    #[default]
+    Undefined,
+    Null,
+    Value(T),
+}
+
+impl<T> Null<T> {
+    pub fn take(self) -> Option<T> {
+        match self {
+            Null::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+    pub fn as_ref(&self) -> Null<&T> {
+        match self {
+            Null::Value(v) => Null::Value(v),
+            Null::Null => Null::Null,
+            Null::Undefined => Null::Undefined,
+        }
+    }
+    pub fn is_some(&self) -> bool {
+        matches!(self, Null::Value(_))
+    }
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+    pub fn is_null(&self) -> bool {
+        matches!(self, Null::Null)
+    }
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Null::Undefined)
+    }
+    pub fn undefined(&self) -> bool {
+        matches!(self, Null::Undefined)
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Null<T> {
+    fn from(v: Result<T, E>) -> Self {
+        match v {
+            Ok(v) => Null::Value(v),
+            Err(_) => Null::Undefined,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Null<T> {
+    fn from(v: Option<T>) -> Self {
+        match v {
+            Some(v) => Null::Value(v),
+            None => Null::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Null<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Null::Value(v) => v.serialize(s),
+            _ => s.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Null<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(Option::<T>::deserialize(d)?.into())
+    }
+}
+
+pub fn new<T>(v: T) -> Null<T> {
+    Null::Value(v)
+}
+pub fn undefined<T>() -> Null<T> {
+    Null::Undefined
+}
+pub fn null<T>() -> Null<T> {
+    Null::Null
+}
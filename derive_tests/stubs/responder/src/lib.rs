@@ -0,0 +1,17 @@
+//! Minimal stand-in for the `responder` crate generated code calls into.
+
+#[derive(Debug)]
+pub struct Error(pub String);
+pub type Result<T> = std::result::Result<T, Error>;
+pub fn to<T: std::fmt::Debug>(v: T) -> Error {
+    Error(format!("{:?}", v))
+}
+pub fn query(e: sqlx::Error) -> Error {
+    Error(e.to_string())
+}
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl actix_web::ResponseError for Error {}
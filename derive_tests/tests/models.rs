@@ -0,0 +1,1002 @@
+use derive_tests::*;
+
+#[test]
+fn composed_model() {
+    let member = Member { email: nulls::Null::Value("a@b.c".to_string()), ..Default::default() };
+    assert!(Member::is_empty_ref(&Member::default()));
+    assert!(!member.is_empty());
+    assert_eq!(Member::MAX_BYTES, Some(4096));
+    assert_eq!(member.get_path("email"), Some(serde_json::json!("a@b.c")));
+    assert_eq!(member.encrypt().decrypt(), member);
+    assert_eq!(Member::__ENCRYPTION_GENERATOR_VERSION, derives::GENERATOR_VERSION);
+    assert_eq!(Member::__JSONB_GENERATOR_VERSION, derives::GENERATOR_VERSION);
+
+    // Configured paths reach the composed generators
+    use derive_tests::ledger::{Ledger, LedgerForm};
+
+    let ledger = Ledger { label: nulls::Null::Value("account-1234".to_string()), ..Default::default() };
+    assert_eq!(ledger.to_masked().label, nulls::Null::Value("****1234".to_string()));
+    let form = LedgerForm { label: nulls::Null::Value(" a ".to_string()), ..Default::default() };
+    assert_eq!(form.sanitize().label, nulls::Null::Value("a".to_string()));
+    let result: derive_tests::errors::Result<()> = ledger.check_size();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn relations() {
+    assert_eq!(Post::join_user(), "LEFT JOIN user ON user.id = post.user_id");
+    assert_eq!(Post::join_readers(), "LEFT JOIN user ON user.post_id = post.id");
+    assert!(Post::select_with_user().starts_with("post.id AS post_id"));
+}
+
+#[test]
+fn query_builder() {
+    let query = user::query::Builder::new()
+        .email_eq("a@b.c")
+        .age_between(1, 5)
+        .id_in(&["1", "2"])
+        .created_by_is_null();
+    assert_eq!(query.sql(), format!("SELECT {} FROM user WHERE user.email = $1 AND user.age >= $2 AND user.age <= $3 AND user.id = ANY($4) AND user.created_by IS NULL", user::alias::ALL));
+    assert!(query.build().is_ok());
+}
+
+#[test]
+fn time_ranges() {
+    let now = chrono::Utc::now();
+    let query = user::query::Builder::new()
+        .created_at_since(now)
+        .created_at_until(now);
+    assert!(query.where_clause().ends_with("WHERE user.created_at >= $1 AND user.created_at < $2"), "{}", query.sql());
+
+    let query = user::query::Builder::new().created_at_range(None, Some(now));
+    assert_eq!(query.where_clause(), "WHERE user.created_at < $1");
+    assert!(query.build().is_ok());
+}
+
+#[test]
+fn public_json() {
+    let user = User::default().set_email("a@b.c").clone_tenant_id(&nulls::Null::Value(String::from("t1")));
+    assert_eq!(user.to_json()["tenant_id"], "t1");
+    assert!(user.to_public_json().get("tenant_id").is_none());
+    assert_eq!(user.to_public_json()["email"], "a@b.c");
+
+    let account = Account::default().set_name("Acme");
+    assert!(account.to_json().get("created_by").is_some());
+    assert!(account.to_public_json().get("created_by").is_none());
+    assert_eq!(Note::default().to_public_json(), Note::default().to_json());
+}
+
+#[test]
+fn projections() {
+    use user::UserSummary;
+
+    assert_eq!(UserSummary::ALIAS_ALL, "user.id AS user_id, user.email AS user_email, user.age AS user_age");
+    assert_eq!(UserSummary::PLAIN_ALL, "id, email, age");
+
+    let summary = UserSummary::from(User::default().set_email("a@b.c").set_nickname("ab"));
+    assert!(summary == UserSummary { email: nulls::Null::Value(String::from("a@b.c")), ..Default::default() });
+    assert_eq!(serde_json::to_value(&summary).unwrap()["email"], "a@b.c");
+    let _: fn(&sqlx::postgres::PgRow) -> UserSummary = UserSummary::parse;
+}
+
+#[test]
+fn option_fields() {
+    let legacy = Legacy::default().set_name("Ada").push_labels("a").set_score(3);
+    assert_eq!(legacy.name, Some(String::from("Ada")));
+    assert_eq!(legacy.labels_ref(), Some(&vec![String::from("a")]));
+    assert_eq!(legacy.touched_fields(), vec!["name", "labels", "score"]);
+    assert!(legacy.update_sql().0.starts_with("UPDATE legacy SET name = $1, labels = $2, score = $3 WHERE id = $4"));
+    assert_eq!(legacy.clone().untouched().name, None);
+
+    assert_eq!(Legacy::RETURNING, "legacy.id AS legacy_id, legacy.created_at AS legacy_created_at");
+    assert!(legacy.update_sql().0.ends_with(&format!("RETURNING {}", Legacy::RETURNING)));
+    assert!(legacy.insert_sql().0.ends_with(&format!("RETURNING {}", Legacy::RETURNING)));
+    assert_eq!(User::RETURNING, User::ALIAS_ALL);
+
+    let legacy = legacy.set_insert_id("sm");
+    assert!(legacy.id.is_some());
+    assert_eq!(legacy.insert_sql().1, 4);
+    let _: fn(&sqlx::postgres::PgRow) -> Legacy = Legacy::parse;
+}
+
+struct Prefixed(&'static str);
+
+impl derives::Cipher for Prefixed {
+    fn encrypt(&self, plaintext: &str) -> String {
+        format!("{}{}", self.0, plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> String {
+        ciphertext.strip_prefix(self.0).unwrap_or(ciphertext).to_string()
+    }
+}
+
+#[test]
+fn zeroized_fields() {
+    use derive_tests::sealed::Sealed;
+    use zeroize::Zeroize;
+
+    let mut sealed = Sealed { api_key: nulls::Null::Value("secret".to_string()), label: nulls::Null::Value("main".to_string()) };
+    assert_eq!(sealed.api_key(), "secret");
+
+    sealed.zeroize();
+    assert_eq!(sealed.api_key, nulls::Null::Value(String::new()));
+    assert_eq!(sealed.label, nulls::Null::Value("main".to_string()));
+    assert_eq!(derive_tests::sealed::SealedForm::from(sealed.decrypt()).label(), "main");
+}
+
+#[test]
+fn integrity_signature() {
+    use derive_tests::signed::Signed;
+
+    let signed = Signed { api_key: nulls::Null::Value("abc".to_string()), ..Default::default() };
+    let encrypted = signed.encrypt();
+    assert!(encrypted.signature.is_some());
+    assert!(encrypted.verify_signature().is_ok());
+    assert_eq!(encrypted.try_decrypt().unwrap().api_key, signed.api_key);
+
+    let tampered = Signed { api_key: nulls::Null::Value("cbb".to_string()), ..encrypted.clone() };
+    let error = tampered.try_decrypt().unwrap_err();
+    assert!(error.to_string().contains("Signed failed integrity verification"));
+    assert!(signed.verify_signature().is_err());
+
+    // Plain decrypt refuses tampered documents too
+    assert_eq!(tampered.decrypt(), tampered);
+
+    // Nested ciphertexts are signed with the document
+    let nested = Signed {
+        credentials: nulls::Null::Value(derive_tests::credentials::Credentials { label: nulls::Null::Value("abc".to_string()), ..Default::default() }),
+        ..signed.clone()
+    };
+    let encrypted = nested.encrypt();
+    assert!(encrypted.verify_signature().is_ok());
+    let swapped = Signed { credentials: nulls::Null::Value(Default::default()), ..encrypted.clone() };
+    assert!(swapped.verify_signature().is_err());
+
+    // Hash setters keep the signature valid
+    let rehashed = encrypted.set_card_number("4242").unwrap();
+    assert!(rehashed.verify_signature().is_ok());
+    assert!(rehashed.verify_card_number("4242"));
+}
+
+#[test]
+fn versioned_envelope() {
+    use derive_tests::envelope::Envelope;
+
+    let envelope = Envelope { api_key: nulls::Null::Value("abc".to_string()), ..Default::default() };
+    let encrypted = envelope.encrypt();
+    assert_eq!(Envelope::CIPHER_VERSION, "v2");
+    assert_eq!(encrypted.api_key, nulls::Null::Value("v2:cba".to_string()));
+    assert!(!encrypted.needs_rotation());
+    assert_eq!(encrypted.decrypt(), envelope);
+
+    let legacy = Envelope { api_key: nulls::Null::Value("v1:ABC".to_string()), ..Default::default() };
+    assert!(legacy.needs_rotation());
+    assert_eq!(legacy.decrypt(), envelope);
+
+    let untagged = Envelope { api_key: nulls::Null::Value("cba".to_string()), ..Default::default() };
+    assert!(untagged.needs_rotation());
+    assert_eq!(untagged.decrypt(), envelope);
+
+    let rotated = legacy.re_encrypt(&Prefixed(""), &Prefixed("k2-"));
+    assert_eq!(rotated.api_key, nulls::Null::Value("v2:k2-ABC".to_string()));
+    assert!(!rotated.needs_rotation());
+}
+
+#[test]
+fn decrypt_failures() {
+    use derive_tests::vault::Vault;
+    use derive_tests::wallet::Wallet;
+
+    let vault = Vault { api_key: nulls::Null::Value("abc".to_string()), ..Default::default() };
+    assert_eq!(vault.encrypt().try_decrypt().unwrap(), vault);
+
+    let corrupt = Vault { api_key: nulls::Null::Value("corrupt".to_string()), ..Default::default() };
+    let error = corrupt.try_decrypt().unwrap_err();
+    assert!(error.to_string().contains("Vault field `api_key`"));
+
+    let wallet = Wallet { backups: nulls::Null::Value(vec![vault.encrypt(), corrupt]), ..Default::default() };
+    assert!(wallet.try_decrypt().is_err());
+}
+
+#[test]
+fn nested_encryption() {
+    use derive_tests::vault::Vault;
+    use derive_tests::wallet::Wallet;
+
+    let vault = Vault { api_key: nulls::Null::Value("abc".to_string()), ..Default::default() };
+    let wallet = Wallet {
+        primary: nulls::Null::Value(vault.clone()),
+        backups: nulls::Null::Value(vec![vault.clone(), Vault::default()]),
+        label: nulls::Null::Value("main".to_string()),
+    };
+
+    let encrypted = wallet.encrypt();
+    assert_eq!(encrypted.primary, nulls::Null::Value(vault.encrypt()));
+    assert_eq!(encrypted.backups, nulls::Null::Value(vec![vault.encrypt(), Vault::default()]));
+    assert_eq!(encrypted.label, wallet.label);
+    assert_eq!(encrypted.decrypt(), wallet);
+    assert_eq!(wallet.to_masked().primary, nulls::Null::Value(vault.to_masked()));
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    assert_eq!(runtime.block_on(wallet.encrypt_async()).unwrap(), encrypted);
+}
+
+#[test]
+fn masked_fields() {
+    let secrets = Secrets {
+        api_key: nulls::Null::Value("sk_live_123".to_string()),
+        card_number: nulls::Null::Value("4242424242421234".to_string()),
+        email: nulls::Null::Value("jane@example.com".to_string()),
+        label: nulls::Null::Value("main".to_string()),
+    };
+
+    let masked = secrets.to_masked();
+    assert_eq!(masked.api_key, nulls::Null::Value("****".to_string()));
+    assert_eq!(masked.card_number, nulls::Null::Value("****1234".to_string()));
+    assert_eq!(masked.email, nulls::Null::Value("j***@example.com".to_string()));
+    assert_eq!(masked.label, secrets.label);
+
+    let short = Secrets { card_number: nulls::Null::Value("12".to_string()), email: nulls::Null::Value("nobody".to_string()), ..Default::default() };
+    assert_eq!(short.to_masked().card_number, nulls::Null::Value("****".to_string()));
+    assert_eq!(short.to_masked().email, nulls::Null::Value("****".to_string()));
+}
+
+#[test]
+fn async_cipher() {
+    use derive_tests::vault::Vault;
+
+    let vault = Vault {
+        api_key: nulls::Null::Value("abc".to_string()),
+        card_number: nulls::Null::Value("4242".to_string()),
+        label: nulls::Null::Value("x".to_string()),
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let encrypted = runtime.block_on(vault.encrypt_async()).unwrap();
+    assert_eq!(encrypted, vault.encrypt());
+    assert_eq!(runtime.block_on(encrypted.decrypt_async()).unwrap(), vault);
+
+    let unreachable = Vault { api_key: nulls::Null::Value("unreachable".to_string()), ..Default::default() };
+    assert!(runtime.block_on(unreachable.encrypt_async()).is_err());
+}
+
+#[test]
+fn deterministic_fields() {
+    let secrets = Secrets { email: nulls::Null::Value("a@b.c".to_string()), ..Default::default() };
+
+    assert_eq!(Secrets::lookup_email("a@b.c"), secrets.encrypt().email);
+    assert_eq!(secrets.encrypt().decrypt().email, secrets.email);
+}
+
+#[test]
+fn hashed_fields() {
+    use derive_tests::credentials::Credentials;
+
+    let credentials = Credentials::default().set_api_key("hunter2").unwrap();
+    assert!(credentials.api_key.is_some());
+    assert_ne!(credentials.api_key, nulls::Null::Value("hunter2".to_string()));
+    assert!(credentials.verify_api_key("hunter2"));
+    assert!(!credentials.verify_api_key("hunter3"));
+    assert_eq!(credentials.decrypt().api_key, credentials.api_key);
+    assert!(!Credentials::default().verify_api_key(""));
+}
+
+#[test]
+fn key_rotation() {
+    use derive_tests::vault::Vault;
+
+    let vault = Vault {
+        api_key: nulls::Null::Value("v1:abc".to_string()),
+        card_number: nulls::Null::Value("v1:4242".to_string()),
+        label: nulls::Null::Value("v1:x".to_string()),
+    };
+
+    let rotated = vault.re_encrypt(&Prefixed("v1:"), &Prefixed("v2:"));
+    assert_eq!(rotated.api_key, nulls::Null::Value("v2:abc".to_string()));
+    assert_eq!(rotated.card_number, vault.card_number);
+    assert_eq!(rotated.label, vault.label);
+
+    let rotated = rotated.re_encrypt_key("payment", &Prefixed("v1:"), &Prefixed("v2:"));
+    assert_eq!(rotated.card_number, nulls::Null::Value("v2:4242".to_string()));
+    assert_eq!(rotated.re_encrypt_key("pii", &Prefixed("v2:"), &Prefixed("v3:")), rotated);
+}
+
+#[test]
+fn cipher_backend() {
+    use derive_tests::vault::Vault;
+
+    let vault = Vault {
+        api_key: nulls::Null::Value("abc".to_string()),
+        card_number: nulls::Null::Value("4242".to_string()),
+        label: nulls::Null::Value("x".to_string()),
+    };
+    let encrypted = vault.encrypt();
+
+    assert_eq!(encrypted.api_key, nulls::Null::Value("cba".to_string()));
+    assert_eq!(encrypted.card_number, nulls::Null::Value("payment:2424".to_string()));
+    assert_eq!(Vault::key_id("card_number"), Some("payment"));
+    assert_eq!(Vault::key_id("api_key"), None);
+    assert_eq!(encrypted.label, vault.label);
+    assert_eq!(encrypted.decrypt(), vault);
+}
+
+#[test]
+fn window_aggregates() {
+    use derive_tests::invoice::{agg, alias, plain};
+
+    assert_eq!(agg::TOTAL_COUNT, "COUNT(*) OVER() AS total_count");
+    assert_eq!(agg::ALIAS_ALL_WITH_TOTAL, format!("{}, {}", alias::ALL, agg::TOTAL_COUNT));
+    assert_eq!(agg::PLAIN_ALL_WITH_TOTAL, format!("{}, {}", plain::ALL, agg::TOTAL_COUNT));
+
+    let (invoices, total) = derive_tests::invoice::parsers::with_total(&[]);
+    assert!(invoices.is_empty());
+    assert_eq!(total, 0);
+}
+
+#[test]
+fn base_columns() {
+    use derive_tests::audit_base::BaseAudit;
+    use derive_tests::invoice::Invoice;
+
+    assert_eq!(Invoice::ALIAS_ALL, "invoices.id AS invoices_id, invoices.created_at AS invoices_created_at, invoices.updated_at AS invoices_updated_at, invoices.total AS invoices_total");
+
+    let invoice = Invoice::new().set_id("inv-1").set_total(42);
+    let base = BaseAudit::from(&invoice);
+    assert_eq!(base.id, nulls::Null::Value("inv-1".to_string()));
+    assert!(base.created_at.is_undefined());
+}
+
+#[test]
+fn constraint_violations() {
+    let error = user::UserDbError::Violation("email_taken");
+    assert_eq!(error.to_string(), "email_taken");
+
+    let error = user::UserDbError::from(sqlx::Error::RowNotFound);
+    assert!(!matches!(error, user::UserDbError::Violation(_)));
+}
+
+#[test]
+fn statement_policy() {
+    assert_eq!(User::QUERY_TIMEOUT, Some(std::time::Duration::from_millis(500)));
+    assert_eq!(User::QUERY_RETRIES, 2);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+    let mut attempts = 0;
+    let result = runtime.block_on(User::guard_query("select", "SELECT 1", || {
+        attempts += 1;
+        let attempt = attempts;
+        async move {
+            match attempt {
+                1 => Err(sqlx::Error::PoolTimedOut),
+                _ => Ok(attempt),
+            }
+        }
+    }));
+    assert_eq!(result.unwrap(), 2);
+
+    let mut attempts = 0;
+    let result = runtime.block_on(User::guard_query("select", "SELECT 1", || {
+        attempts += 1;
+        async { Err::<(), _>(sqlx::Error::RowNotFound) }
+    }));
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(attempts, 1);
+
+    let result = runtime.block_on(User::guard_query("select", "SELECT 1", || async {
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        Ok(())
+    }));
+    assert!(matches!(result, Err(sqlx::Error::Io(e)) if e.to_string() == "select on user timed out"));
+
+    let mut attempts = 0;
+    let result = runtime.block_on(User::guard_query("insert", "INSERT", || {
+        attempts += 1;
+        async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok(())
+        }
+    }));
+    assert!(matches!(result, Err(sqlx::Error::Io(_))));
+    assert_eq!(attempts, 1);
+
+    let mut attempts = 0;
+    let result = runtime.block_on(User::guard_query("insert", "INSERT", || {
+        attempts += 1;
+        async { Err::<(), _>(sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset))) }
+    }));
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+}
+
+#[test]
+fn advisory_locks() {
+    // Locks only accept a transaction, a pool would release them as soon as the statement ends
+    fn locked<'c>(
+        user: &'c User,
+        transaction: &'c mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> impl std::future::Future<Output = responder::Result<bool>> + 'c {
+        user.try_lock(transaction)
+    }
+    fn lock<'c>(
+        user: &'c User,
+        transaction: &'c mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> impl std::future::Future<Output = responder::Result<()>> + 'c {
+        user.lock(transaction)
+    }
+    let _ = (locked, lock);
+}
+
+#[test]
+fn precomputed_statements() {
+    let legacy = Legacy::default().set_id("l1").set_name("Ada").set_labels(vec!["a"]).set_score(1);
+    assert_eq!(legacy.update_sql().0, Legacy::UPDATE_ALL_SQL);
+    assert_eq!(Legacy::UPDATE_ALL_SILENT_SQL, "UPDATE legacy SET name = $1, labels = $2, score = $3 WHERE id = $4");
+    assert_eq!(legacy.insert_sql(), (Legacy::INSERT_ALL_SQL.to_string(), 4));
+    assert_eq!(
+        Legacy::INSERT_ALL_SQL,
+        "INSERT INTO legacy (id, name, labels, score, created_at) VALUES ($1, $2, $3, $4, now()) RETURNING legacy.id AS legacy_id, legacy.created_at AS legacy_created_at"
+    );
+
+    // Partial records still build their own statement
+    let partial = legacy.clone().clear_score();
+    assert!(partial.update_sql().0.starts_with("UPDATE legacy SET name = $1, labels = $2 WHERE id = $3"));
+
+    assert!(User::UPDATE_ALL_SILENT_SQL.ends_with("updated_at = now(), version = version + 1 WHERE id = $7 AND version = $8"), "{}", User::UPDATE_ALL_SILENT_SQL);
+}
+
+#[test]
+fn parse_ordinal() {
+    use models::tags::{self, Tag};
+
+    assert_eq!(tags::plain::ALL, "id, name, (lower(name)) AS slug, rank");
+    let _: fn(&sqlx::postgres::PgRow) -> Tag = Tag::parse_ordinal;
+    let _: fn(&sqlx::postgres::PgRow) -> Legacy = Legacy::parse_ordinal;
+}
+
+#[test]
+fn model() {
+    assert_eq!(Document::TABLE, "documents");
+    assert_eq!(document::alias::doc::ALL, "documents.id AS doc_id, documents.title AS doc_title");
+}
+
+#[test]
+fn order() {
+    use user::order::Sort;
+    assert_eq!(user::order::EMAIL_DESC, "user.email DESC");
+    assert_eq!("createdAtDesc".parse::<Sort>(), Ok(Sort::CreatedAtDesc));
+    assert_eq!(Sort::from_parts("created_at", "asc"), Some(Sort::CreatedAtAsc));
+    assert_eq!(Sort::from_parts("nope", "asc"), None);
+    assert_eq!(Sort::EmailAsc.to_sql(), "user.email ASC");
+}
+
+#[test]
+fn arrays() {
+    let user = User::default().push_tags("a").push_tags("b");
+    assert_eq!(user.tags(), Some(vec!["a".to_string(), "b".to_string()]));
+    let query = user::query::Builder::new().tags_any("a").tags_array_contains(&["a", "b"]);
+    assert!(query.sql().ends_with("WHERE $1 = ANY(user.tags) AND user.tags @> $2"));
+}
+
+#[test]
+fn admin() {
+    let _ = User::admin_routes();
+    assert_eq!(User::sql_comment(), "");
+
+    // Listing is paged, sorts outside the columns fall back to the first one
+    let paging = user::admin::Paging::from_pairs([("page", "3"), ("perPage", "10"), ("sort", "email; DROP"), ("order", "desc")]);
+    assert_eq!((paging.offset(), paging.limit(), paging.sort.as_str()), (20, 10, "id"));
+    let paging = user::admin::Paging::from_pairs([("sort", "email")]);
+    assert_eq!(user::order::Sort::from_parts(&paging.sort, &paging.order).map(|s| s.to_sql()), Some(user::order::EMAIL_ASC));
+}
+
+#[test]
+fn enums() {
+    let query = user::query::Builder::new().status_eq(Status::Inactive).status_in(&[Status::Active]);
+    assert!(query.build().is_ok());
+}
+
+#[test]
+fn jsonb() {
+    let profile = Profile { bio: "hello".to_string(), links: vec!["a".to_string()] };
+    let query = user::query::Builder::new().profile_contains(profile);
+    assert!(query.build().is_ok());
+}
+
+#[test]
+fn stats() {
+    let _ = user::User::stats;
+    assert_eq!(user::stats::Stats::default().row_estimate, 0);
+}
+
+#[test]
+fn id_strategy() {
+    let post = Post::default().set_insert_id();
+    assert_eq!(post.id, nulls::new("post-1".to_string()));
+    let user = User::default().set_insert_id();
+    assert!(user.id.is_some());
+}
+
+#[test]
+fn mysql() {
+    assert_eq!(account::tabled::NAME, "`accounts`.`name`");
+    assert_eq!(account::alias::ID, "`accounts`.`id` AS `accounts_id`");
+    let account = Account::default().set_name("a");
+    assert_eq!(account.name(), Some("a".to_string()));
+}
+
+#[test]
+fn tenant_scoping() {
+    let post = Post::default().set_id("p1").set_user_id("u1");
+    assert_eq!(
+        post.debug_update_sql(),
+        "UPDATE post SET user_id = 'u1' WHERE id = 'p1' AND user_id = 'u1' RETURNING ".to_string() + Post::ALIAS_ALL
+    );
+
+    let sql = post::query::Builder::new("u1").id_eq("p1").sql();
+    assert!(sql.ends_with("WHERE post.user_id = $1 AND post.id = $2"));
+
+    // Every read and delete of a scoped model takes the tenant
+    fn scoped(pool: &sqlx::AnyPool) -> impl std::future::Future<Output = responder::Result<Vec<Post>>> + '_ {
+        post::any::fetch_all(pool, "u1")
+    }
+    let _ = scoped;
+    let _ = Post::admin_routes();
+}
+
+#[test]
+fn generator_version() {
+    assert_eq!(User::__POSTGRESQL_GENERATOR_VERSION, derives::GENERATOR_VERSION);
+    assert_eq!(Secrets::__ENCRYPTION_GENERATOR_VERSION, derives::GENERATOR_VERSION);
+    assert_eq!(Status::__ENUMS_GENERATOR_VERSION, derives::GENERATOR_VERSION);
+    assert_eq!(User::__POSTGRESQL_CAPABILITIES, &["actix", "admin", "sqlcommenter", "tracing"]);
+    assert_eq!(derives::capabilities(), &["actix", "admin", "audit", "sqlcommenter", "tracing", "utoipa"]);
+}
+
+#[test]
+fn ignored_columns() {
+    use models::tags::{self, Tag};
+
+    let tag = Tag::default().set_id("t1").clone_usage(&nulls::Null::Value(3));
+    assert_eq!(tag.usage(), Some(3));
+    assert!(!Tag::ALIAS_ALL.contains("usage"), "{}", Tag::ALIAS_ALL);
+    assert!(!tags::plain::ALL.contains("usage"));
+    assert!(!tag.update_sql().0.contains("usage"));
+    assert!(!account::alias::ALL.contains("selected"));
+}
+
+#[test]
+fn computed_columns() {
+    use models::tags::{self, Tag};
+
+    assert_eq!(tags::alias::SLUG, "(lower(name)) AS tag_slug");
+    assert_eq!(tags::plain::SLUG, "(lower(name)) AS slug");
+    assert_eq!(tags::tabled::SLUG, "(lower(name))");
+    assert_eq!(tags::alias::parent::SLUG, "(lower(name)) AS parent_slug");
+
+    let sql = Tag::default().set_id("t1").set_name("Rust").debug_update_sql();
+    assert!(!sql.contains("slug ="), "{}", sql);
+    assert!(tags::query::Builder::new().slug_eq("rust").sql().ends_with("WHERE (lower(name)) = $1"));
+}
+
+#[test]
+fn parse_with() {
+    use models::tags::{self, Tag};
+
+    assert_eq!(tags::alias::RANK, "tag.rank AS tag_rank");
+    let _: fn(&sqlx::postgres::PgRow) -> Tag = Tag::parse;
+}
+
+#[test]
+fn alias_wrappers() {
+    use models::tags::{Tag, TagAsParent};
+
+    let _: fn(&sqlx::postgres::PgRow) -> user::UserAsAuthor = user::UserAsAuthor::parse;
+    let _: fn(&sqlx::postgres::PgRow) -> user::UserAsEditor = user::UserAsEditor::parse;
+
+    let mut parent = TagAsParent(Tag::default().set_name("Rust"));
+    parent.name = nulls::Null::Value(String::from("Go"));
+    assert_eq!(parent.name(), Some(String::from("Go")));
+    assert_eq!(Tag::from(parent).name(), Some(String::from("Go")));
+}
+
+#[test]
+fn retention() {
+    assert_eq!(User::RETENTION_DAYS, 90);
+    fn purge(pool: &sqlx::PgPool) -> impl std::future::Future<Output = responder::Result<u64>> + Send + '_ {
+        User::purge_expired_with(pool, 500, |_| {})
+    }
+    let _ = purge;
+}
+
+#[test]
+fn trace_query() {
+    let output = futures::executor::block_on(Post::trace_query("select", "SELECT 1", async { 7 }));
+    assert_eq!(output, 7);
+}
+
+#[test]
+fn query_metrics() {
+    futures::executor::block_on(Post::trace_query("delete", "DELETE FROM post", async {}));
+    assert_eq!(post::OBSERVED.with(|o| o.borrow().clone()), vec!["post:delete"]);
+}
+
+#[test]
+fn sql_previews() {
+    let post = Post::default().set_id("p1").set_user_id("u1");
+    assert_eq!(
+        post.update_sql(),
+        ("UPDATE post SET user_id = $1 WHERE id = $2 AND user_id = $3 RETURNING ".to_string() + Post::ALIAS_ALL, 3)
+    );
+    assert_eq!(
+        post.insert_sql(),
+        ("INSERT INTO post (id, user_id) VALUES ($1, $2) RETURNING ".to_string() + Post::ALIAS_ALL, 2)
+    );
+    assert_eq!(Post::default().update_sql(), (String::new(), 0));
+}
+
+#[test]
+fn debug_update_sql() {
+    let user = User::default().set_id("u1").set_email("o'neil@example.com").set_age(3);
+    let sql = user.debug_update_sql();
+    assert!(sql.contains("email = 'o''neil@example.com'"), "{}", sql);
+    assert!(sql.contains("age = 3"), "{}", sql);
+    assert!(sql.contains("WHERE id = 'u1'"), "{}", sql);
+    assert!(!sql.contains('$'), "{}", sql);
+    assert!(!sql.contains("nickname ="), "{}", sql);
+}
+
+#[test]
+fn tracked_changes() {
+    use derive_tests::draft::Draft;
+
+    // Loaded rows have every column set and nothing touched
+    let loaded = Draft {
+        id: nulls::Null::Value("d1".to_string()),
+        title: nulls::Null::Value("Draft".to_string()),
+        body: nulls::Null::Value("Body".to_string()),
+        ..Default::default()
+    };
+    assert!(loaded.touched_fields().is_empty());
+    assert_eq!(loaded.update_sql().0, "");
+
+    let edited = loaded.clone().set_title("Final");
+    assert_eq!(edited.touched_fields(), vec!["title"]);
+    assert_eq!(edited, Draft { title: nulls::Null::Value("Final".to_string()), ..loaded.clone() });
+    assert!(edited.update_sql().0.starts_with("UPDATE draft SET title = $1, updated_at = now() WHERE id = $2"));
+    assert!(edited.untouched().touched_fields().is_empty());
+}
+
+#[test]
+fn tracked_update() {
+    let loaded = Member {
+        id: nulls::Null::Value("m1".to_string()),
+        email: nulls::Null::Value("a@b.c".to_string()),
+        label: nulls::Null::Value("old".to_string()),
+        ..Default::default()
+    };
+    assert!(loaded.touched_fields().is_empty());
+
+    // Copies from a form touch every field they set
+    let mut mutated = loaded.clone();
+    mutated.mutate(&Member { label: nulls::Null::Value("new".to_string()), ..loaded.clone() });
+    assert_eq!(mutated.touched_fields(), vec!["email", "label"]);
+    assert!(mutated.update_sql().0.starts_with("UPDATE members SET email = $1, label = $2 WHERE id = $3"));
+
+    let form = derive_tests::member::MemberForm { label: nulls::Null::Value("new".to_string()), ..Default::default() };
+    assert_eq!(Member::from(form).touched_fields(), vec!["label"]);
+
+    // Deserialized bodies, as admin updates receive them, touch the fields they hold
+    let body: Member = serde_json::from_value(serde_json::json!({ "label": "new" })).unwrap();
+    assert!(body.touched_fields().is_empty());
+    let body = body.touch_all();
+    assert_eq!(body.touched_fields(), vec!["label"]);
+    assert!(body.update_sql().0.starts_with("UPDATE members SET label = $1 WHERE id = $2"));
+}
+
+#[test]
+fn touched_fields() {
+    let user = User::default().set_id("u1").set_email("a").set_nickname("n");
+    assert_eq!(user.touched_fields(), vec!["email", "nickname"]);
+    assert!(user.untouched().touched_fields().is_empty());
+}
+
+#[test]
+fn form_filter() {
+    let form = search::UserSearchForm {
+        email: nulls::new(" A@B.com ".to_string()),
+        age: nulls::new(3),
+        ..Default::default()
+    };
+    let sql = form.to_filter().sql();
+    assert!(sql.ends_with("WHERE user.email = $1 AND user.age > $2"), "{}", sql);
+}
+
+#[test]
+fn sqlite() {
+    assert_eq!(note::alias::BODY, "\"note\".\"body\" AS \"note_body\"");
+    assert_eq!(Note::default().set_body("x").body(), Some("x".to_string()));
+}
+
+#[test]
+fn any_driver() {
+    assert_eq!(post::any::ALL, "post.id AS post_id, post.user_id AS post_user_id, post.password_hash AS post_password_hash");
+    let _ = post::any::update;
+}
+
+#[test]
+fn enum_values() {
+    assert_eq!(user::values::STATUS, &["Active", "Inactive"]);
+    assert!(User::validate_status("inactive").is_ok());
+    assert!(User::validate_status("gone").is_err());
+}
+
+#[test]
+fn form_example() {
+    let example = search::UserSearchForm::example_json();
+    assert_eq!(example["email"], "jane@doe.com");
+    assert_eq!(example["age"], 30);
+
+    // The example is set on the schema of the same name
+    use utoipa::Modify;
+    let mut openapi = utoipa::openapi::OpenApiBuilder::new()
+        .components(Some(utoipa::openapi::ComponentsBuilder::new()
+            .schema("UserSearchForm", utoipa::openapi::ObjectBuilder::new())
+            .build()))
+        .build();
+    search::UserSearchFormExample.modify(&mut openapi);
+    let schema = serde_json::to_value(&openapi.components.unwrap().schemas["UserSearchForm"]).unwrap();
+    assert_eq!(schema["examples"][0], example);
+}
+
+#[test]
+fn decrypt_audit() {
+    Secrets::default().decrypt_with("req-1");
+    Secrets::default().decrypt();
+    secrets::AUDIT.with(|a| assert_eq!(*a.borrow(), vec!["Secrets:api_key,card_number,email:req-1", "Secrets:api_key,card_number,email:"]));
+}
+
+#[test]
+fn error_messages() {
+    let error = derive_tests::search::UserSearchForm::default().to_error().reject_age();
+    assert_eq!(error.age, nulls::Null::Value("Age must be positive".to_string()));
+    assert!(error.validate().is_err());
+
+    let error = derive_tests::secrets::SecretsForm::default().to_error().reject_api_key();
+    assert_eq!(error.api_key, nulls::Null::Value("API key is required".to_string()));
+
+    // Fields declaring a message are required
+    let error = derive_tests::secrets::SecretsForm::default().to_error();
+    assert_eq!(error.api_key, nulls::Null::Value("API key is required".to_string()));
+    assert!(error.validate().is_err());
+    let form = derive_tests::secrets::SecretsForm { api_key: nulls::new("k".to_string()), ..Default::default() };
+    assert!(form.to_error().validate().is_ok());
+}
+
+#[test]
+fn encryption_intake() {
+    let form = secrets::SecretsForm { api_key: nulls::new("k".to_string()), label: nulls::new("a".to_string()), ..Default::default() };
+    let secrets = Secrets::intake(form).unwrap();
+    assert_eq!(secrets.label, nulls::new("a".to_string()));
+
+    // Validation runs on the sanitized form, before anything is encrypted
+    let form = secrets::SecretsForm { label: nulls::new("a".to_string()), ..Default::default() };
+    assert!(Secrets::intake(form).unwrap_err().to_string().contains("API key is required"));
+}
+
+#[test]
+fn shared_to() {
+    use derives::To;
+
+    let form: secrets::SecretsForm = Secrets::default().to();
+    assert_eq!(form, secrets::SecretsForm::default());
+
+    // Every expanding derive implements it once, Model for its composed derives too
+    fn implements<T: To>() {}
+    implements::<User>();
+    implements::<page::Page<String>>();
+    implements::<Member>();
+    implements::<member::MemberForm>();
+    implements::<account::Account>();
+    implements::<search::UserSearchForm>();
+}
+
+#[test]
+fn nested_model() {
+    assert_eq!(models::tags::Tag::TABLE, "tag");
+    let _ = models::tags::parsers::parent::parse;
+    let _ = models::tags::query::Builder::new().name_eq("x");
+}
+
+#[test]
+fn enum_flags() {
+    let set = PermissionSet::from(Permission::Read).with(Permission::Write);
+    assert_eq!(set.to_string(), "Read|Write");
+    assert_eq!("write|read".parse::<PermissionSet>(), Ok(set));
+    assert!("read|nope".parse::<PermissionSet>().is_err());
+    assert_eq!((set & PermissionSet::from(Permission::Write)).bits(), 2);
+    assert_eq!(PermissionSet::ALL.bits(), 7);
+    assert_eq!(serde_json::to_string(&set).unwrap(), "\"Read|Write\"");
+}
+
+#[test]
+fn healthcheck() {
+    let _ = user::User::healthcheck;
+    assert_eq!(user::User::HEALTHCHECK_TIMEOUT.as_secs(), 2);
+}
+
+#[test]
+fn compare() {
+    let a = User::default().set_id("1").set_email("a").set_nickname("x");
+    let b = User::default().set_id("1").set_email("a").set_nickname("y");
+    assert!(a.same_identity(&b));
+    assert!(a.same_content(&b));
+    assert!(!a.same_content(&b.clone().set_email("b")));
+}
+
+#[test]
+fn enum_map() {
+    assert_eq!(Status::VARIANT_NAMES, &["Active", "Inactive"]);
+    let map = Status::map(|s| s.to_string().len());
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&Status::Inactive], 8);
+}
+
+#[test]
+fn jsonb_path() {
+    let profile = Profile { bio: "hi".to_string(), links: vec!["x".to_string()] };
+    assert_eq!(profile.get_path("bio"), Some(serde_json::json!("hi")));
+    assert_eq!(profile.get_path_as::<String>("links.0"), Some("x".to_string()));
+    assert_eq!(profile.get_path("links.3"), None);
+    assert_eq!(profile.get_path_as::<i32>("bio"), None);
+}
+
+#[test]
+fn jsonb_size_guard() {
+    let settings = settings::Settings { theme: "dark".to_string(), notes: vec!["x".repeat(100)] };
+    let value = settings.to_bounded_json().unwrap();
+    assert!(value.to_string().len() <= 32, "{}", value);
+    assert_eq!(value["theme"], "dark");
+    assert!(settings.check_size().is_ok());
+
+    let limits = limits::Limits { label: "y".repeat(20) };
+    assert!(limits.check_size().is_err());
+    assert!(limits::Limits::default().check_size().is_ok());
+    assert_eq!(Profile::MAX_BYTES, None);
+}
+
+#[test]
+fn jsonb_encode() {
+    let profile = Profile { bio: "hi".to_string(), links: vec!["x".to_string()] };
+    let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+    assert!(sqlx::Encode::<sqlx::Postgres>::encode_by_ref(&profile, &mut buf).is_ok());
+    assert_eq!(buf[1..], serde_json::to_vec(&profile).unwrap()[..]);
+
+    let limits = limits::Limits { label: "y".repeat(20) };
+    let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+    assert!(sqlx::Encode::<sqlx::Postgres>::encode_by_ref(&limits, &mut buf).is_err());
+}
+
+#[test]
+fn diff() {
+    let old = User::default().set_id("u1").set_email("a").set_age(1);
+    let new = old.clone().set_email("b").set_nickname("n");
+    assert_eq!(old.diff(&new), vec![
+        ("email", serde_json::json!("a"), serde_json::json!("b")),
+        ("nickname", serde_json::Value::Null, serde_json::json!("n")),
+    ]);
+    assert!(old.diff(&old).is_empty());
+}
+
+#[test]
+fn is_empty_ref() {
+    let contact = address::Contact { name: "a".to_string(), ..Default::default() };
+    assert_eq!(serde_json::to_value(&contact).unwrap(), serde_json::json!({ "name": "a" }));
+}
+
+#[test]
+fn ref_getters() {
+    let user = User::default().set_email("a").set_tags(vec!["x"]);
+    assert_eq!(user.email_ref(), Some(&"a".to_string()));
+    assert_eq!(user.tags_ref().map(|t| t.len()), Some(1));
+    assert_eq!(user.age_ref(), None);
+}
+
+#[test]
+fn column_visibility() {
+    let post = Post { password_hash: nulls::new("h".to_string()), ..Default::default() };
+    assert_eq!(post::password_hash(&post), Some(&"h".to_string()));
+}
+
+#[test]
+fn pagination() {
+    let params = paging::ListParams::from_pairs([("page", "3"), ("perPage", "500"), ("sort", "email"), ("order", "ASC")]);
+    assert_eq!((params.page, params.per_page, params.sort.as_str(), params.order.as_str()), (3, 50, "email", "asc"));
+    assert_eq!(params.offset(), 100);
+
+    let params = paging::ListParams::from_pairs([("page", "-1"), ("sort", "password"), ("order", "x")]);
+    assert_eq!((params.page, params.per_page, params.sort.as_str(), params.order.as_str()), (1, 10, "created_at", "desc"));
+}
+
+#[actix_web::test]
+async fn pagination_extractor() {
+    use actix_web::FromRequest;
+
+    let req = actix_web::test::TestRequest::with_uri("/users?page=2&per_page=5").to_http_request();
+    let params = paging::ListParams::extract(&req).await.unwrap();
+    assert_eq!((params.page, params.limit()), (2, 5));
+}
+
+#[test]
+fn builder() {
+    let user = User::new().set_email("a").set_age(2).build();
+    assert_eq!(user.id().map(|id| id.len()), Some(36));
+    assert!(user.created_at().is_some() && user.updated_at().is_some());
+    assert_eq!(user.email(), Some("a".to_string()));
+
+    let post = Post::new().build();
+    assert_eq!(post.id(), Some("post-1".to_string()));
+}
+
+#[test]
+fn parse_prefixed() {
+    let _: fn(&sqlx::postgres::PgRow, &str) -> User = User::parse_prefixed;
+    let _: fn(&sqlx::postgres::PgRow, &str) -> User = user::parsers::prefixed;
+}
+
+#[test]
+fn copy_in() {
+    drop(User::copy_in(&[]));
+    assert_eq!(User::COPY_CHUNK_BYTES, 1 << 20);
+}
+
+#[test]
+fn typed_db_error() {
+    let error = user::UserDbError::from(sqlx::Error::RowNotFound);
+    assert!(matches!(error, user::UserDbError::NotFound));
+    assert_eq!(user::UserDbError::Stale.to_string(), "Record in user table was modified by another transaction");
+    let error: responder::Error = user::UserDbError::Unchanged.into();
+    assert!(error.0.contains("No modified columns"));
+}
+
+#[test]
+fn notify() {
+    async fn listen(pool: &sqlx::PgPool) -> responder::Result<String> {
+        let mut listener = User::listen(pool).await?;
+        User::next_changed(&mut listener).await
+    }
+
+    let _ = listen;
+    drop(User::default().notify_changed());
+}
+
+#[test]
+fn update_silent() {
+    drop(User::default().update_silent());
+    drop(Post::default().update_silent());
+}
+
+#[test]
+fn generic_model() {
+    let page = page::Page::<String>::new().set_records(vec!["a".to_string()]).push_records("b");
+    assert_eq!(page.records_ref().map(|r| r.len()), Some(2));
+    assert_eq!(page::Page::<String>::TABLE, "pages");
+    let sql = page::query::Builder::<String>::new().records_any("a").sql();
+    assert!(sql.starts_with("SELECT pages.id AS pages_id"), "{}", sql);
+}
+
+#[test]
+fn serde_renames() {
+    assert_eq!(page::api::TITLE, "pageTitle");
+    assert_eq!(page::plain::TITLE, "page_title");
+    assert_eq!(page::api::CAPTION_TEXT, "label");
+    assert_eq!(page::plain::CAPTION_TEXT, "caption");
+    let page = page::Page::<String>::default().set_title("a").set_caption_text("b");
+    let sql = page.debug_update_sql();
+    assert!(sql.contains("page_title = 'a', caption = 'b'"), "{}", sql);
+
+    // Versions are bumped and checked through their column name
+    let page = page.set_id("p1").set_version(3);
+    assert!(page.update_sql().0.contains("revision = revision + 1 WHERE id = $3 AND revision = $4 RETURNING"), "{}", page.update_sql().0);
+    assert!(page.debug_update_sql().contains("revision = revision + 1 WHERE id = 'p1' AND revision = 3 RETURNING"), "{}", page.debug_update_sql());
+}
@@ -0,0 +1,105 @@
+//! Expansion costs of the shared generators, run with `cargo bench -p derive_utils`.
+//!
+//! Every group expands a 20 column `Null<Vec<String>>` model, the shape of the larger models of
+//! a service crate, and compares the shared path against the work it replaced.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use derive_utils::Compose;
+use syn::{parse_quote, DeriveInput, Type};
+
+#[derive(Default, deluxe::ExtractAttributes)]
+#[deluxe(attributes(column))]
+#[allow(dead_code)]
+struct ColumnAttrs {
+    readonly: deluxe::Flag,
+}
+
+// The generators a `Model` composes next to PostgreSQL
+const COMPOSED: Compose = Compose {
+    version: "0.0.1",
+    actix: false,
+    audit: false,
+    skip: &["getters", "is_empty", "parsers", "responder", "sqlx"],
+    responder: None,
+    nulls: None,
+};
+
+fn model() -> DeriveInput {
+    let fields = (0..20).map(|i| {
+        let name = quote::format_ident!("field_{}", i as usize);
+        quote::quote!(#[column] pub #name: Null<Vec<String>>,)
+    });
+
+    parse_quote! {
+        #[encryption(cipher = "ciphers::Aes")]
+        #[jsonb(max_bytes = 4096)]
+        pub struct Model {
+            #(#fields)*
+            #[encryption]
+            pub email: Null<String>,
+        }
+    }
+}
+
+// The inner type lookup before its pattern was compiled once and shared
+fn parse_inner_type_uncached(ty: &Type) -> Option<Type> {
+    let input = derive_utils::derive_type_to_string(ty);
+    let pattern = regex::Regex::new(r"^[^<]*<(.+)>$").unwrap();
+
+    match pattern.captures(&input) {
+        Some(captures) => syn::parse_str(captures.get(1)?.as_str()).ok(),
+        None => syn::parse_str(&input).ok(),
+    }
+}
+
+fn parse_inner_type(c: &mut Criterion) {
+    let ty: Type = parse_quote!(Null<Vec<String>>);
+    let mut group = c.benchmark_group("parse_inner_type x20");
+
+    group.bench_function("compiled per call", |b| b.iter(|| {
+        for _ in 0..20 {
+            black_box(parse_inner_type_uncached(black_box(&ty)));
+        }
+    }));
+
+    group.bench_function("cached", |b| b.iter(|| {
+        for _ in 0..20 {
+            black_box(derive_utils::derive_parse_inner_type(black_box(&ty)).ok());
+        }
+    }));
+
+    group.finish();
+}
+
+fn all_fields(c: &mut Criterion) {
+    let ast = model();
+
+    c.bench_function("all_fields", |b| b.iter(|| {
+        black_box(derive_utils::derive_all_fields::<&str, ColumnAttrs>(black_box(&ast), "column"))
+    }));
+}
+
+fn composed(c: &mut Criterion) {
+    let ast = model();
+    let expand = |ast: &DeriveInput| {
+        black_box(derive_utils::encryption::derive_encryption(ast, &COMPOSED).ok());
+        black_box(derive_utils::is_empty::derive_is_empty(ast, &COMPOSED).ok());
+        black_box(derive_utils::jsonb::derive_jsonb(ast, &COMPOSED).ok());
+    };
+    let mut group = c.benchmark_group("composed Model");
+
+    // Each generator parsing its own copy of the input, as separately stacked derives do
+    group.bench_function("re-parsed per generator", |b| b.iter(|| {
+        for _ in 0..3 {
+            black_box(syn::parse2::<DeriveInput>(quote::quote!(#ast)).ok());
+        }
+
+        expand(&ast);
+    }));
+
+    group.bench_function("parsed once", |b| b.iter(|| expand(&ast)));
+
+    group.finish();
+}
+
+criterion_group!(benches, parse_inner_type, all_fields, composed);
+criterion_main!(benches);
@@ -0,0 +1,714 @@
+//! Shared generation of the `MySQL` and `Sqlite` derives, which only differ by dialect.
+use crate::FieldKind;
+use proc_macro2::{Ident, TokenStream as TS2};
+use quote::format_ident;
+use syn::{DeriveInput, LitStr};
+
+/// The differences between the SQL backends sharing [`derive_sql_model`].
+pub struct Dialect {
+    /// The derive's name, used in errors and generator constants, e.g. `MySQL`.
+    pub name: &'static str,
+    /// The version of the derive crate, embedded into generated types.
+    pub version: &'static str,
+    /// The character quoting identifiers, doubled to escape it.
+    pub quote: char,
+    /// The bind parameter placeholder.
+    pub placeholder: &'static str,
+    /// The expression stamping timestamp columns.
+    pub now: &'static str,
+    /// The path of the sqlx row type, e.g. `sqlx::mysql::MySqlRow`.
+    pub row: &'static str,
+    /// The path of the sqlx pool type, e.g. `sqlx::MySqlPool`.
+    pub pool: &'static str,
+    /// Whether writes return the written row, it is read back by id otherwise.
+    pub returning: bool,
+    /// Whether the actix `Responder` implementation is generated.
+    pub actix: bool,
+}
+
+impl Dialect {
+    /// Quotes an identifier, escaping the quote character by doubling it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use derive_utils::dialect::Dialect;
+    ///
+    /// let dialect = Dialect {
+    ///     name: "MySQL",
+    ///     version: "0.0.1",
+    ///     quote: '`',
+    ///     placeholder: "?",
+    ///     now: "NOW()",
+    ///     row: "sqlx::mysql::MySqlRow",
+    ///     pool: "sqlx::MySqlPool",
+    ///     returning: false,
+    ///     actix: false,
+    /// };
+    /// assert_eq!(dialect.quote("user"), "`user`");
+    /// assert_eq!(dialect.quote("a`b"), "`a``b`");
+    /// ```
+    pub fn quote(&self, identifier: &str) -> String {
+        let quote = self.quote.to_string();
+
+        format!("{0}{1}{0}", quote, identifier.replace(&quote, &quote.repeat(2)))
+    }
+}
+
+// Table attribute
+crate::attr_spec! {
+    #[deluxe(attributes(table))]
+    struct TableAttrs {
+        rename: Option<LitStr>,
+        responder: Option<LitStr>,
+        nulls: Option<LitStr>,
+    }
+}
+
+// Column attribute
+crate::attr_spec! {
+    #[deluxe(attributes(column))]
+    struct ColumnAttrs {
+        skip_compare: deluxe::Flag,
+        created_at: deluxe::Flag,
+        updated_at: deluxe::Flag,
+        skip_update: deluxe::Flag,
+        readonly: deluxe::Flag,
+        ignore: deluxe::Flag,
+        private: deluxe::Flag,
+    }
+}
+
+/// Generates the model implementation of a `MySQL` or `Sqlite` derive.
+///
+/// # Arguments
+/// - `stream`: The derive input.
+/// - `dialect`: The backend the generated code targets.
+///
+/// # Returns
+/// - The generated tokens, or the error to report at the offending attribute.
+///
+/// # Example
+/// ```rust
+/// use derive_utils::dialect::{derive_sql_model, Dialect};
+///
+/// let dialect = Dialect {
+///     name: "Sqlite",
+///     version: "0.0.1",
+///     quote: '"',
+///     placeholder: "?",
+///     now: "CURRENT_TIMESTAMP",
+///     row: "sqlx::sqlite::SqliteRow",
+///     pool: "sqlx::SqlitePool",
+///     returning: true,
+///     actix: false,
+/// };
+/// let tokens = derive_sql_model(quote::quote! {
+///     struct User {
+///         #[column]
+///         id: Null<String>,
+///         #[column]
+///         name: Null<String>,
+///     }
+/// }, &dialect);
+/// assert!(tokens.is_ok());
+///
+/// // Columns are named after fields, so tuple structs are rejected
+/// let error = derive_sql_model(quote::quote! {
+///     struct User(Null<String>);
+/// }, &dialect).unwrap_err().to_string();
+/// assert!(error.contains("can only be derived for structs with named fields"));
+/// ```
+pub fn derive_sql_model(stream: TS2, dialect: &Dialect) -> deluxe::Result<TS2> {
+    // Parse token stream
+    let ast: DeriveInput = syn::parse2(stream)?;
+    let node = &ast.ident.clone();
+
+    // Columns are named after fields, so only named structs are supported
+    crate::derive_named_fields(&ast, dialect.name)?;
+
+    // Create backend types
+    let row = syn::parse_str::<syn::Path>(dialect.row)?;
+    let pool = syn::parse_str::<syn::Path>(dialect.pool)?;
+    let placeholder = dialect.placeholder;
+    let now = dialect.now;
+
+    // Create main token stream
+    let mut token = quote::quote!{};
+    let table_attrs = crate::derive_struct_attrs::<TableAttrs>(&ast);
+
+    // Validate attributes against their registered grammar
+    crate::derive_validate_attrs::<TableAttrs>(&ast.attrs)?;
+    crate::derive_validate_fields::<ColumnAttrs>(&ast)?;
+
+    // Create table name
+    let table_name = crate::derive_snake_case(table_attrs.rename
+        .map(|s| s.value())
+        .unwrap_or(node.to_string()));
+    let quoted_table = dialect.quote(&table_name);
+
+    // Create error module path, defaults to the responder crate
+    let responder = match &table_attrs.responder {
+        Some(path) => path.parse::<syn::Path>()?,
+        None => syn::parse_quote!(responder)
+    };
+
+    // Create nulls crate path, defaults to the nulls crate
+    let nulls = match &table_attrs.nulls {
+        Some(path) => path.parse::<syn::Path>()?,
+        None => syn::parse_quote!(nulls)
+    };
+
+    // Create error message
+    let error = format!("No matching record(s) found in {} table", table_name);
+    let unchanged = format!("No modified columns to update in {} table", table_name);
+    let unidentified = format!("Cannot update a {} record without an id", table_name);
+
+    // Retrieve all fields once and pre-allocate for them
+    let fields = crate::derive_all_fields::<&str, ColumnAttrs>(&ast, "column");
+    let size = fields.len();
+
+    // All column attributed information
+    let mut all_props = Vec::<TS2>::with_capacity(size);  // Specify types explicitly
+    let mut all_setters = Vec::<TS2>::with_capacity(size);
+    let mut all_setter_opts = Vec::<TS2>::with_capacity(size);
+    let mut all_clones = Vec::<TS2>::with_capacity(size);
+    let mut all_clears = Vec::<TS2>::with_capacity(size);
+    let mut all_cleable_fields = Vec::<Ident>::with_capacity(size);
+    let mut all_update_fields = Vec::<Ident>::with_capacity(size);
+    let mut all_update_columns = Vec::<String>::with_capacity(size);
+    let mut all_update_timestamps = Vec::<String>::with_capacity(size);
+    let mut all_insert_fields = Vec::<Ident>::with_capacity(size);
+    let mut all_insert_columns = Vec::<String>::with_capacity(size);
+    let mut all_insert_timestamps = Vec::<String>::with_capacity(size);
+    let mut all_compared_fields = Vec::<Ident>::with_capacity(size);
+    let mut has_id = false;
+
+    let mut all_private_keys = Vec::<String>::with_capacity(size);
+    let mut all_attributed_fields = Vec::<Ident>::with_capacity(size);
+    let mut all_attributed_inner_ty = Vec::<syn::Type>::with_capacity(size);
+
+    // Set text values
+    let mut all_const_names = Vec::<Ident>::with_capacity(size);
+    let mut all_aliased = Vec::<String>::with_capacity(size);
+    let mut all_renamed = Vec::<String>::with_capacity(size);
+    let mut all_plain = Vec::<String>::with_capacity(size);
+    let mut all_tabled = Vec::<String>::with_capacity(size);
+
+    // Loop through all fields
+    for (
+        field,
+        ty,
+        is_attributed,
+        attrs
+    ) in fields {
+        let kind = crate::derive_field_kind(&ty);
+        let inner_ty = kind.inner().clone();
+        let inner_ty_str = crate::derive_normalize_type(&inner_ty);
+        let is_string = matches!(kind, FieldKind::Plain(_)) && inner_ty_str == "String";
+        let is_timestamp = attrs.created_at.is_set() || attrs.updated_at.is_set();
+
+        // Ignored fields only exist in memory and are never read or written
+        let is_attributed = is_attributed && !attrs.ignore.is_set();
+
+        // Private fields are left out of public JSON under their serialized name
+        if attrs.private.is_set() {
+            all_private_keys.push(crate::derive_serde_name(&ast, &field));
+        }
+
+        // Set compared fields
+        if field.to_string().as_str() == "id" {
+            has_id = true;
+        }
+
+        if !attrs.skip_compare.is_set() && !is_timestamp {
+            all_compared_fields.push(field.clone());
+        }
+
+        // Set all insert fields
+        let quoted = dialect.quote(&field.to_string());
+
+        if is_attributed && is_timestamp {
+            all_insert_timestamps.push(quoted.clone());
+        } else if is_attributed {
+            all_insert_fields.push(field.clone());
+            all_insert_columns.push(quoted.clone());
+        }
+
+        // Set all update fields
+        if is_attributed && attrs.updated_at.is_set() {
+            all_update_timestamps.push(format!("{} = {}", quoted, now));
+        } else if field.to_string().as_str() != "id"
+            && is_attributed
+            && !attrs.skip_update.is_set()
+            && !attrs.readonly.is_set()
+            && !is_timestamp
+        {
+            all_update_fields.push(field.clone());
+            all_update_columns.push(format!("{} = {}", quoted, placeholder));
+        }
+
+        // Create props along with borrowing getters
+        let ref_name = format_ident!("{}_ref", field);
+        match kind.is_null() {
+            true => all_props.push(quote::quote! {
+                pub fn #field(&self) -> Option<#inner_ty> {
+                    self.#field.clone().take()
+                }
+
+                pub fn #ref_name(&self) -> Option<&#inner_ty> {
+                    self.#field.as_ref().take()
+                }
+            }),
+            false => all_props.push(quote::quote! {
+                pub fn #field(&self) -> #ty {
+                    self.#field.clone()
+                }
+
+                pub fn #ref_name(&self) -> &#ty {
+                    &self.#field
+                }
+            })
+        }
+
+        // Create setters unless the column is readonly or ignored
+        if !attrs.readonly.is_set() && !attrs.ignore.is_set() {
+            // Create setter_opts
+            let setter_opt_name = format_ident!("set_opts_{}", field.clone());
+            all_setter_opts.push(quote::quote! {
+                pub fn #setter_opt_name(mut self, value: &Option<#inner_ty>) -> Self {
+                    if let Some(value) = value.clone() {
+                        self.#field = #nulls::new(value);
+                    }
+
+                    self
+                }
+            });
+
+            // Create setters
+            let setter_name = format_ident!("set_{}", field.clone());
+
+            match inner_ty_str.as_str() {
+                "String" => {
+                    all_setters.push(quote::quote! {
+                        pub fn #setter_name<T: ToString>(mut self, value: T) -> Self {
+                            self.#field = #nulls::new(value.to_string());
+
+                            self
+                        }
+                    });
+                },
+                _ => {
+                    all_setters.push(quote::quote! {
+                        pub fn #setter_name(mut self, value: #inner_ty) -> Self {
+                            self.#field = #nulls::new(value);
+
+                            self
+                        }
+                    });
+                }
+            }
+        }
+
+        // All clones
+        let clone_name = format_ident!("clone_{}", field.clone());
+        match is_string {
+            true => all_clones.push(quote::quote! {
+                pub fn #clone_name(mut self, value: #ty) -> Self {
+                    self.#field = value;
+
+                    self
+                }
+            }),
+            false => all_clones.push(quote::quote! {
+                pub fn #clone_name(mut self, value: &#ty) -> Self {
+                    self.#field = value.clone();
+
+                    self
+                }
+            })
+        }
+
+        // All Null ⟶ Undefined
+        let clear_name = format_ident!("clear_{}", field.clone());
+        if kind.is_null() {
+            all_cleable_fields.push(field.clone());
+            all_clears.push(quote::quote! {
+                pub fn #clear_name(mut self) -> Self {
+                    self.#field = #nulls::undefined();
+
+                    self
+                }
+            });
+        }
+
+        // Check if is_attributed
+        if is_attributed {
+            // Create basic table names and aliases
+            let plain = crate::derive_snake_case(field.clone().to_string());
+            let renamed = format!("{}_{}", table_name, plain);
+            let tabled = format!("{}.{}", quoted_table, dialect.quote(&plain));
+            let aliased = format!("{} AS {}", tabled, dialect.quote(&renamed));
+
+            all_attributed_fields.push(field.clone());
+            all_attributed_inner_ty.push(inner_ty.clone());
+
+            all_const_names.push(format_ident!("{}", plain.to_uppercase()));
+            all_aliased.push(aliased);
+            all_plain.push(plain.clone());
+            all_renamed.push(renamed.clone());
+            all_tabled.push(tabled.clone());
+        }
+    }
+
+    // Use explicit string join with &str type
+    let all_aliased_str = all_aliased.join(", ");
+    let private_len = all_private_keys.len();
+    let all_plain_str = all_plain.join(", ");
+    let all_renamed_str = all_renamed.join(", ");
+    let all_tabled_str = all_tabled.join(", ");
+
+    // Create identity comparison
+    //____________________________________________________________
+    let same_identity = match has_id {
+        true => quote::quote! {
+            pub fn same_identity(&self, other: &Self) -> bool {
+                self.id.is_some() && self.id == other.id
+            }
+        },
+        false => quote::quote! {}
+    };
+
+    // Create Constant Sub-modules
+    //____________________________________________________________
+    token.extend(quote::quote!{
+        pub mod alias {
+            pub const ALL: &'static str = #all_aliased_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_aliased;
+            )*
+        }
+
+        pub mod plain {
+            pub const ALL: &'static str = #all_plain_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_plain;
+            )*
+        }
+
+        pub mod renamed {
+            pub const ALL: &'static str = #all_renamed_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_renamed;
+            )*
+        }
+
+        pub mod tabled {
+            pub const ALL: &'static str = #all_tabled_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_tabled;
+            )*
+        }
+    });
+
+    // Create Parser Sub-module
+    //____________________________________________________________
+    token.extend(quote::quote!{
+        pub mod parsers {
+            use #nulls::Null;
+            use sqlx::Result;
+
+            use super::#node;
+
+            pub fn parse(row: &#row) -> #node {
+                #node::parse(row)
+            }
+
+            pub fn result(row: Result<#row>) -> #responder::Result<#node> {
+                let result = row.map_err(#responder::query)?;
+                let row = parse(&result);
+
+                match !row.is_empty() {
+                    true => Ok(row),
+                    false => Err(#responder::to(#error))
+                }
+            }
+
+            pub fn relational(row: &#row) -> Null<#node> {
+                let row = parse(row);
+
+                match row.is_empty() {
+                    true => #nulls::undefined(),
+                    false => #nulls::new(row)
+                }
+            }
+        }
+    });
+
+    // Create Node Related implementations
+    //____________________________________________________________
+    token.extend(quote::quote!{
+        impl #node {
+            pub const TABLE: &'static str = #table_name;
+            pub const ALIAS_ALL: &'static str = #all_aliased_str;
+
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+
+            #same_identity
+
+            pub fn same_content(&self, other: &Self) -> bool {
+                true #(&& self.#all_compared_fields == other.#all_compared_fields)*
+            }
+
+            pub fn to_json(&self) -> serde_json::Value {
+                serde_json::to_value(self)
+                    .unwrap_or(serde_json::Value::Null)
+            }
+
+            pub fn to_public_json(&self) -> serde_json::Value {
+                let mut value = self.to_json();
+                let private: [&str; #private_len] = [#(#all_private_keys),*];
+
+                if let serde_json::Value::Object(map) = &mut value {
+                    for key in private {
+                        map.remove(key);
+                    }
+                }
+
+                value
+            }
+
+            #(#all_props)*
+
+            #(#all_setters)*
+
+            #(#all_setter_opts)*
+
+            #(#all_clones)*
+
+            #(#all_clears)*
+
+            pub fn clear_all(mut self) -> Self {
+                #(
+                    if !self.#all_cleable_fields.is_some() {
+                        self.#all_cleable_fields =  #nulls::undefined();
+                    }
+                )*
+
+                self
+            }
+
+            pub fn parse(row: &#row) -> Self {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#all_attributed_fields = #nulls::Null::from(row.try_get::<#all_attributed_inner_ty, &str>(#all_renamed));
+                )*
+
+                data
+            }
+        }
+    });
+
+    // Create Insert and Update implementations, backends without RETURNING
+    // read the written record back by id
+    //____________________________________________________________
+    if has_id {
+        let select = format!(
+            "SELECT {{}} FROM {} WHERE {}.{} = {}",
+            quoted_table, quoted_table, dialect.quote("id"), placeholder
+        );
+        let (insert_sql, update_sql) = match dialect.returning {
+            true => (
+                format!("INSERT INTO {} ({{}}) VALUES ({{}}) RETURNING {{}}", quoted_table),
+                format!("UPDATE {} SET {{}} WHERE {} = {} RETURNING {{}}", quoted_table, dialect.quote("id"), placeholder)
+            ),
+            false => (
+                format!("INSERT INTO {} ({{}}) VALUES ({{}})", quoted_table),
+                format!("UPDATE {} SET {{}} WHERE {} = {}", quoted_table, dialect.quote("id"), placeholder)
+            )
+        };
+        let (inserted, updated) = match dialect.returning {
+            true => (
+                quote::quote! {
+                    let sql = format!(#insert_sql, columns.join(", "), values.join(", "), Self::ALIAS_ALL);
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !self.#all_insert_fields.undefined() {
+                            query = query.bind(self.#all_insert_fields());
+                        }
+                    )*
+
+                    parsers::result(query.fetch_one(pool).await)
+                },
+                quote::quote! {
+                    let sql = format!(#update_sql, updates.join(", "), Self::ALIAS_ALL);
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !self.#all_update_fields.undefined() {
+                            query = query.bind(self.#all_update_fields());
+                        }
+                    )*
+
+                    parsers::result(query.bind(id).fetch_one(pool).await)
+                }
+            ),
+            false => (
+                quote::quote! {
+                    let sql = format!(#insert_sql, columns.join(", "), values.join(", "));
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !self.#all_insert_fields.undefined() {
+                            query = query.bind(self.#all_insert_fields());
+                        }
+                    )*
+
+                    let result = query.execute(pool).await.map_err(#responder::query)?;
+
+                    // Rows without an id were given one by AUTO_INCREMENT
+                    match self.id() {
+                        Some(id) => Self::find(pool, id).await,
+                        None => match result.last_insert_id() {
+                            0 => Err(#responder::to(#error)),
+                            id => Self::find(pool, id).await
+                        }
+                    }
+                },
+                quote::quote! {
+                    let sql = format!(#update_sql, updates.join(", "));
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !self.#all_update_fields.undefined() {
+                            query = query.bind(self.#all_update_fields());
+                        }
+                    )*
+
+                    let result = query
+                        .bind(id.clone())
+                        .execute(pool)
+                        .await
+                        .map_err(#responder::query)?;
+
+                    match result.rows_affected() {
+                        0 => Err(#responder::to(#error)),
+                        _ => Self::find(pool, id).await
+                    }
+                }
+            )
+        };
+
+        token.extend(quote::quote!{
+            impl #node {
+                pub async fn find(pool: &#pool, id: impl ToString) -> #responder::Result<Self> {
+                    let sql = format!(#select, Self::ALIAS_ALL);
+
+                    parsers::result(sqlx::query(&sql)
+                        .bind(id.to_string())
+                        .fetch_one(pool)
+                        .await)
+                }
+
+                pub async fn insert(&self, pool: &#pool) -> #responder::Result<Self> {
+                    let mut columns = Vec::<String>::new();
+                    let mut values = Vec::<String>::new();
+
+                    #(
+                        if !self.#all_insert_fields.undefined() {
+                            columns.push(#all_insert_columns.to_string());
+                            values.push(#placeholder.to_string());
+                        }
+                    )*
+
+                    #(
+                        columns.push(#all_insert_timestamps.to_string());
+                        values.push(#now.to_string());
+                    )*
+
+                    #inserted
+                }
+
+                pub async fn update(&self, pool: &#pool) -> #responder::Result<Self> {
+                    let Some(id) = self.id() else {
+                        return Err(#responder::to(#unidentified));
+                    };
+
+                    let mut updates = Vec::<String>::new();  // Specify type explicitly
+
+                    #(
+                        if !self.#all_update_fields.undefined() {
+                            updates.push(#all_update_columns.to_string());
+                        }
+                    )*
+
+                    // Timestamps only follow actual changes
+                    if updates.is_empty() {
+                        return Err(#responder::to(#unchanged));
+                    }
+
+                    #(
+                        updates.push(#all_update_timestamps.to_string());
+                    )*
+
+                    #updated
+                }
+            }
+        });
+    }
+
+    // Create FromRow implementation
+    //____________________________________________________________
+    token.extend(quote::quote!{
+        impl<'r> sqlx::FromRow<'r, #row> for #node {
+            fn from_row(row: &'r #row) -> sqlx::Result<Self> {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#all_attributed_fields = #nulls::Null::from(row.try_get::<#all_attributed_inner_ty, &str>(#all_plain));
+                )*
+
+                Ok(data)
+            }
+        }
+    });
+
+    // Create Responder implementation
+    //____________________________________________________________
+    if dialect.actix {
+        token.extend(quote::quote!{
+            impl actix_web::Responder for #node {
+                type Body = actix_web::body::BoxBody;
+
+                fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+                    actix_web::HttpResponse::Ok().json(serde_json::json!({
+                        "code": 200,
+                        "data": self
+                    }))
+                }
+            }
+        });
+    }
+
+    // Create To implementation
+    token.extend(quote::quote!{
+        impl derives::To for #node {}
+    });
+
+    // Create generator constants
+    token.extend(crate::derive_generator_consts(&ast, dialect.name, dialect.version, &[("actix", dialect.actix)]));
+
+    // Return the new token
+    Ok(token)
+}
@@ -0,0 +1,1493 @@
+//! Shared generation of the `Encryption` derive, standalone or composed by `Model`.
+use crate::Compose;
+use proc_macro2::TokenStream as TS2;
+use quote::format_ident;
+use syn::{punctuated::Punctuated, DeriveInput, LitBool, LitStr, Token, Type};
+
+crate::attr_spec! {
+    #[deluxe(attributes(encryption))]
+    struct EncryptionAttrs {
+        sanitize: Option<LitStr>,
+        errors: Option<Type>,
+        skip: Option<LitBool>,
+        fpe: deluxe::Flag,
+        error_message: Option<LitStr>,
+        key: Option<LitStr>,
+        hash: Option<LitStr>,
+        deterministic: deluxe::Flag,
+        mask: Option<LitStr>,
+        nested: deluxe::Flag,
+    }
+}
+
+crate::attr_spec! {
+    #[deluxe(attributes(encryption))]
+    pub struct EncryptionStructAttrs {
+        audit: Option<syn::Path>,
+        cipher: Option<LitStr>,
+        #[deluxe(rename = async)]
+        is_async: deluxe::Flag,
+        version: Option<LitStr>,
+        legacy: Option<LitStr>,
+        integrity: deluxe::Flag,
+        zeroize: deluxe::Flag,
+    }
+}
+
+// Parse legacy ciphers as comma separated `tag = path` pairs
+fn derive_legacies(legacy: Option<&LitStr>, is_versioned: bool) -> deluxe::Result<Vec<(String, syn::Path)>> {
+    let Some(legacy) = legacy else {
+        return Ok(vec![]);
+    };
+
+    if !is_versioned {
+        return Err(syn::Error::new(legacy.span(), "legacy requires a current version, set #[encryption(version = \"...\")]"));
+    }
+
+    let mut legacies = vec![];
+
+    for pair in legacy.value().split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let Some((tag, path)) = pair.split_once('=') else {
+            return Err(syn::Error::new(legacy.span(), format!("legacy `{}` must be written as `tag = path`", pair)));
+        };
+
+        let path = syn::parse_str::<syn::Path>(path.trim())
+            .map_err(|e| syn::Error::new(legacy.span(), e.to_string()))?;
+
+        legacies.push((tag.trim().to_string(), path));
+    }
+
+    Ok(legacies)
+}
+
+// Create the recursion applying `call` to a nested field, the items of `Vec` fields and through `Null` or `Option`
+fn derive_nested(field: &syn::Ident, ty: &Type, call: &TS2, nulls: &syn::Path) -> TS2 {
+    let apply = |value: TS2, ty: &Type| match crate::derive_field_kind(ty) {
+        crate::FieldKind::Vec(_) => quote::quote! {{
+            let mut values = Vec::with_capacity(#value.len());
+
+            for child in #value.iter() {
+                values.push(child.#call);
+            }
+
+            values
+        }},
+        _ => quote::quote!(#value.#call)
+    };
+
+    match crate::derive_field_kind(ty) {
+        crate::FieldKind::Null(inner) => {
+            let value = apply(quote::quote!(value), &inner);
+
+            quote::quote! {
+                if let #nulls::Null::Value(value) = &self.#field {
+                    data.#field = #nulls::Null::Value(#value);
+                }
+            }
+        },
+        crate::FieldKind::Option(inner) => {
+            let value = apply(quote::quote!(value), &inner);
+
+            quote::quote! {
+                if let Some(value) = &self.#field {
+                    data.#field = Some(#value);
+                }
+            }
+        },
+        _ => {
+            let value = apply(quote::quote!(self.#field), ty);
+
+            quote::quote! {
+                data.#field = #value;
+            }
+        }
+    }
+}
+
+// Create the mask replacing a field in `to_masked`
+fn derive_masker(field: &syn::Ident, mask: Option<&LitStr>, is_encrypted: bool, nulls: &syn::Path) -> deluxe::Result<Option<TS2>> {
+    let masked = match mask.map(|m| m.value()).as_deref() {
+        Some("full") => quote::quote!("****".to_string()),
+        Some("last4") => quote::quote! {{
+            let chars = value.chars().collect::<Vec<char>>();
+
+            match chars.len() > 4 {
+                true => format!("****{}", chars[chars.len() - 4..].iter().collect::<String>()),
+                false => "****".to_string()
+            }
+        }},
+        Some("email") => quote::quote! {
+            match value.split_once('@').and_then(|(local, domain)| local.chars().next().map(|first| (first, domain))) {
+                Some((first, domain)) => format!("{}***@{}", first, domain),
+                None => "****".to_string()
+            }
+        },
+        Some(value) => return Err(syn::Error::new(
+            mask.map(|m| m.span()).unwrap_or_else(|| field.span()),
+            format!("unknown mask `{}`, expected one of: email, last4, full", value)
+        )),
+        None if is_encrypted => quote::quote!("****".to_string()),
+        None => return Ok(None)
+    };
+
+    Ok(Some(quote::quote! {
+        if let #nulls::Null::Value(value) = &self.#field {
+            data.#field = #nulls::Null::Value(#masked);
+        }
+    }))
+}
+
+// Create the setter hashing a one-way field and its verifier
+fn derive_hasher(field: &syn::Ident, hash: &LitStr, resign: &TS2, compose: &Compose) -> deluxe::Result<TS2> {
+    let (responder, nulls) = (compose.responder(), compose.nulls());
+    let setter = format_ident!("set_{}", field);
+    let verifier = format_ident!("verify_{}", field);
+
+    let (hashing, verifying) = match hash.value().as_str() {
+        "argon2" => (
+            quote::quote! {
+                use argon2::password_hash::{rand_core::OsRng, PasswordHasher, SaltString};
+
+                let salt = SaltString::generate(&mut OsRng);
+                argon2::Argon2::default()
+                    .hash_password(plain.to_string().as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| #responder::to(e.to_string()))?
+            },
+            quote::quote! {
+                use argon2::password_hash::{PasswordHash, PasswordVerifier};
+
+                PasswordHash::new(hash).is_ok_and(|hash| argon2::Argon2::default()
+                    .verify_password(candidate.to_string().as_bytes(), &hash)
+                    .is_ok())
+            }
+        ),
+        value => return Err(syn::Error::new(
+            hash.span(),
+            format!("unknown hash `{}`, expected one of: argon2", value)
+        ))
+    };
+
+    Ok(quote::quote! {
+        /// Hashes the plain value into the field, it cannot be decrypted afterwards.
+        ///
+        /// # Parameters
+        /// - `plain`: The plain value to hash.
+        ///
+        /// Signed documents are re-signed, the hash being part of the signature.
+        ///
+        /// # Returns
+        /// - `Ok(Self)` with the field holding the encoded hash.
+        /// - `Err(responder::Error)` if hashing fails.
+        pub fn #setter<Value: ToString>(mut self, plain: Value) -> #responder::Result<Self> {
+            let hash = { #hashing };
+            self.#field = #nulls::Null::Value(hash);
+            #resign
+
+            Ok(self)
+        }
+
+        /// Checks a candidate against the hash stored in the field.
+        ///
+        /// # Parameters
+        /// - `candidate`: The plain value to verify.
+        ///
+        /// # Returns
+        /// - `true` if the candidate matches the stored hash.
+        /// - `false` if it does not or the field holds no hash.
+        pub fn #verifier<Value: ToString>(&self, candidate: Value) -> bool {
+            match &self.#field {
+                #nulls::Null::Value(hash) => { #verifying },
+                _ => false
+            }
+        }
+    })
+}
+
+// Find the #[column] of a field that still generates a setter, stacked SQL derives would clash with a hash setter
+fn derive_column_setter<'a>(ast: &'a DeriveInput, field: &syn::Ident) -> Option<&'a syn::Attribute> {
+    let syn::Data::Struct(data) = &ast.data else {
+        return None;
+    };
+
+    data.fields.iter()
+        .filter(|f| f.ident.as_ref() == Some(field))
+        .flat_map(|f| &f.attrs)
+        .filter(|attr| attr.path().is_ident("column"))
+        .find(|attr| !attr
+            .parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+            .is_ok_and(|metas| metas.iter().any(|meta| {
+                ["no_setter", "readonly", "ignore", "computed", "tsvector"].iter().any(|key| meta.path().is_ident(key))
+            }))
+        )
+}
+
+/// Expands the `Encryption` derive, leaving out the blocks another composed derive owns.
+///
+/// # Arguments
+/// - `ast`: The parsed struct the derive is applied to.
+/// - `compose`: Whether it expands standalone or composed, along with the active features.
+///
+/// # Returns
+/// - The generated tokens, or the error to report at the offending attribute.
+///
+/// # Example
+/// ```rust
+/// use derive_utils::Compose;
+/// use derive_utils::encryption::derive_encryption;
+///
+/// let compose = Compose { version: "0.0.1", actix: false, audit: false, skip: &[], responder: None, nulls: None };
+/// let secret: syn::DeriveInput = syn::parse_quote! {
+///     #[encryption(cipher = "ciphers::Aes", version = "v2", legacy = "v1 = ciphers::Legacy", integrity)]
+///     struct Secret {
+///         #[encryption(error_message = "Token is required")]
+///         token: Null<String>,
+///         signature: Null<String>,
+///     }
+/// };
+/// assert!(derive_encryption(&secret, &compose).is_ok());
+///
+/// // Integrity needs a plain signature field to keep the signature in
+/// let unsigned: syn::DeriveInput = syn::parse_quote! {
+///     #[encryption(cipher = "ciphers::Aes", integrity)]
+///     struct Secret {
+///         #[encryption]
+///         token: Null<String>,
+///     }
+/// };
+/// let error = derive_encryption(&unsigned, &compose).unwrap_err().to_string();
+/// assert!(error.contains("requires an unencrypted `signature: Null<String>` field"));
+/// ```
+pub fn derive_encryption(ast: &DeriveInput, compose: &Compose) -> deluxe::Result<TS2> {
+    let node = &ast.ident.clone();
+
+    // Create the crate paths generated code names, configured by the composing derive
+    let (responder, nulls) = (compose.responder(), compose.nulls());
+    let skip_undefined = format!("{}::Null::undefined", quote::quote!(#nulls)).replace(' ', "");
+
+    // Create main token stream
+    let mut token = quote::quote!{};
+    let node_form = format_ident!("{}Form", node);
+    let node_error = format_ident!("{}Error", node);
+
+    // Validate attributes against their registered grammar
+    crate::derive_validate_attrs::<EncryptionStructAttrs>(&ast.attrs)?;
+    crate::derive_validate_fields::<EncryptionAttrs>(ast)?;
+
+    // Malformed attribute values are reported, a mistyped cipher must not fall back to the default
+    let struct_attrs = crate::derive_try_struct_attrs::<EncryptionStructAttrs>(ast)?;
+    let cipher = match &struct_attrs.cipher {
+        Some(cipher) => Some(cipher.parse::<syn::Path>()?),
+        None => None
+    };
+
+    // Deterministic fields call the backend's `encrypt_deterministic`, `DeterministicExt` by default
+    let (deterministic_encrypt, deterministic_decrypt) = match &cipher {
+        Some(cipher) => (
+            quote::quote!(#cipher::encrypt_deterministic),
+            quote::quote!(#cipher::decrypt_deterministic)
+        ),
+        None => (
+            quote::quote!(ciphers::DeterministicExt::encrypt_deterministic),
+            quote::quote!(ciphers::DeterministicExt::decrypt_deterministic)
+        )
+    };
+
+    // Hashes are part of the signed payload, so hash setters of signed documents re-sign
+    let resign = match (struct_attrs.integrity.is_set(), &cipher) {
+        (true, Some(cipher)) => quote::quote! {
+            self.signature = #nulls::Null::Value(#cipher::sign(&self.signature_payload()));
+        },
+        _ => quote::quote! {}
+    };
+
+    // Create encoding error
+    let error = format!("Unable to parse {} jsonb object", node);
+
+    // All column attributed information
+    let mut all_column_fields = vec![];
+    let mut all_column_kinds = vec![];
+    let mut all_column_inner_types = vec![];
+    let mut is_tracked = false;
+    let mut all_form_struct_fields = vec![];
+    let mut all_error_struct_fields = vec![];
+
+    let mut all_form_props = vec![];
+    let mut sanitizers = vec![];
+    let mut rejections = vec![];
+    let mut validations = vec![];
+
+    let mut all_attributed_fields = vec![];
+    let mut all_attributed_inner_types = vec![];
+    let mut all_attributed_kinds = vec![];
+    let mut all_cipher_fields = vec![];
+    let mut all_fpe_fields = vec![];
+    let mut all_keyed_fields = vec![];
+    let mut all_key_ids = vec![];
+    let mut hashers = vec![];
+    let mut all_hashed_fields = vec![];
+    let mut all_deterministic_fields = vec![];
+    let mut lookups = vec![];
+    let mut maskers = vec![];
+    let mut all_nested = vec![];
+
+    // Loop through all fields
+    for (
+        field,
+        ty,
+        is_attributed,
+        attrs
+    ) in
+        crate::derive_all_fields::<&str, EncryptionAttrs>(ast, "encryption")
+    {
+        // Tracked models keep their `touched` field out of forms, copies touch what they set
+        if field == "touched" && !is_attributed {
+            is_tracked = true;
+            continue;
+        }
+
+        // Retrieve inner type
+        let kind = crate::derive_field_kind(&ty);
+        let inner_ty = kind.inner().clone();
+        let error_type = attrs.errors.clone()
+            .unwrap_or(ty.clone());
+
+        // Include all column fields
+        all_column_fields.push(field.clone());
+        all_column_kinds.push(kind.clone());
+        all_column_inner_types.push(inner_ty.clone());
+
+        // Check all attributed fields
+        let is_skipped = if let Some(b) = attrs.skip.clone() {
+            b.value()
+        } else {
+            false
+        };
+
+        // Nested fields derive Encryption themselves, so each method recurses into them
+        if attrs.nested.is_set() && !is_skipped {
+            if attrs.fpe.is_set() || attrs.key.is_some() || attrs.hash.is_some() || attrs.deterministic.is_set() || attrs.mask.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!("nested field `{}` cannot be combined with fpe, key, hash, deterministic or mask", field)
+                ));
+            }
+
+            all_nested.push((field.clone(), ty.clone()));
+        } else if let (true, false, Some(hash)) = (is_attributed, is_skipped, &attrs.hash) {
+            if attrs.fpe.is_set() || attrs.key.is_some() {
+                return Err(syn::Error::new(
+                    hash.span(),
+                    format!("hash on field `{}` cannot be combined with fpe or key", field)
+                ));
+            }
+
+            if let Some(column) = derive_column_setter(ast, &field) {
+                return Err(syn::Error::new_spanned(
+                    column,
+                    format!("hashed field `{0}` also gets `set_{0}` from its #[column], add #[column(no_setter)]", field)
+                ));
+            }
+
+            hashers.push(derive_hasher(&field, hash, &resign, compose)?);
+            all_hashed_fields.push(field.clone());
+        } else if is_attributed && !is_skipped {
+            all_attributed_fields.push(field.clone());
+            all_attributed_inner_types.push(inner_ty.clone());
+            all_attributed_kinds.push(crate::derive_field_kind(&ty));
+
+            // Format-preserving fields keep their length and charset once encrypted, keyed fields use a named key
+            // and deterministic fields encrypt equal values alike so their ciphertext can be looked up
+            match (attrs.deterministic.is_set(), attrs.fpe.is_set(), &attrs.key) {
+                (true, true, _) | (true, _, Some(_)) => return Err(syn::Error::new(
+                    field.span(),
+                    format!("deterministic field `{}` cannot be combined with fpe or key", field)
+                )),
+                (true, false, None) => {
+                    let lookup = format_ident!("lookup_{}", field);
+                    lookups.push(quote::quote! {
+                        /// Encrypts a value the way the field is encrypted, for equality lookups on the ciphertext.
+                        ///
+                        /// # Parameters
+                        /// - `value`: The plain value to look up.
+                        ///
+                        /// # Returns
+                        /// - The ciphertext to bind, e.g. in `WHERE column = $1`.
+                        pub fn #lookup<Value: Into<#inner_ty>>(value: Value) -> #ty {
+                            let value: #ty = #nulls::Null::Value(value.into());
+                            #deterministic_encrypt(&value)
+                        }
+                    });
+
+                    all_deterministic_fields.push(field.clone());
+                },
+                (false, true, Some(key)) => return Err(syn::Error::new(
+                    key.span(),
+                    format!("key is not supported on format-preserving field `{}`", field)
+                )),
+                (false, true, None) => all_fpe_fields.push(field.clone()),
+                (false, false, Some(key)) => {
+                    if cipher.is_none() {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            format!("key on field `{}` requires a cipher backend, set #[encryption(cipher = \"...\")] on {}", field, node)
+                        ));
+                    }
+
+                    all_keyed_fields.push(field.clone());
+                    all_key_ids.push(key.value());
+                },
+                (false, false, None) => all_cipher_fields.push(field.clone())
+            }
+        }
+
+        // Create masks, encrypted fields are fully masked unless declared otherwise
+        let is_masked = is_attributed && !is_skipped && !attrs.nested.is_set();
+        if let Some(masker) = derive_masker(&field, attrs.mask.as_ref(), is_masked, &nulls)? {
+            maskers.push(masker);
+        }
+
+        // Create form fields
+        all_form_struct_fields.push(quote::quote!{
+            #[serde(skip_serializing_if = #skip_undefined)]
+            pub #field: #ty
+        });
+
+        all_form_props.push(quote::quote! {
+            pub fn #field(&self) -> #inner_ty {
+                self.#field.clone().take().unwrap_or_default()
+            }
+        });
+
+        // Set sanitizers
+        if let Some(attr) = attrs.sanitize {
+            match attr.value().as_str() {
+                "lowercase" => sanitizers.push(quote::quote! {
+                            if let #nulls::Null::Value(value) = data.#field.clone() {
+                                if !value.is_empty() {
+                                    data.#field = #nulls::Null::Value(value.to_string().trim().to_lowercase().to_string());
+                                }
+                            }
+                        }),
+                "normalize_name" => sanitizers.push(quote::quote! {
+                            if let #nulls::Null::Value(value) = data.#field.clone() {
+                                let value = value.trim();
+
+                                if !value.is_empty() {
+                                    data.#field = #nulls::Null::Value(title_case::title_case(&value, "Jr Sr I II III IV V VI VII VIII IX X XX XXX De Los DeLos"));
+                                }
+                            }
+                        }),
+                "trim" => sanitizers.push(quote::quote! {
+                            if let #nulls::Null::Value(value) = data.#field.clone() {
+                                if !value.is_empty() {
+                                    data.#field = #nulls::Null::Value(value.to_string().trim().to_string());
+                                }
+                            }
+                        }),
+                "trim_slash" => sanitizers.push(quote::quote! {
+                            if let #nulls::Null::Value(value) = data.#field.clone() {
+                                if !value.is_empty() {
+                                    data.#field = #nulls::Null::Value(value
+                                        .to_string()
+                                        .trim()
+                                        .trim_end_matches('/')
+                                        .trim()
+                                        .to_string());
+                                }
+                            }
+                        }),
+                _ => {}
+            }
+        }
+
+        // Create error fields
+        all_error_struct_fields.push(quote::quote!{
+            #[serde(skip_serializing_if = #skip_undefined)]
+            pub #field: #error_type
+        });
+
+        // Set error messages, read through serde so any error type holding a string works,
+        // rejecting fields that hold no value when the form is validated
+        if let Some(message) = attrs.error_message.clone() {
+            let reject = format_ident!("reject_{}", field);
+            let is_unset = match &kind {
+                crate::FieldKind::Null(_) => quote::quote! {
+                    !matches!(&self.#field, #nulls::Null::Value(value) if *value != <#inner_ty>::default())
+                },
+                crate::FieldKind::Option(_) => quote::quote! {
+                    !matches!(&self.#field, Some(value) if *value != <#inner_ty>::default())
+                },
+                _ => quote::quote!(self.#field == <#ty>::default())
+            };
+
+            validations.push(quote::quote! {
+                if #is_unset {
+                    error = error.#reject();
+                }
+            });
+            rejections.push(quote::quote! {
+                /// Marks the field as invalid using the message declared next to it.
+                ///
+                /// # Returns
+                /// - A copy of the error with the field populated.
+                pub fn #reject(mut self) -> Self {
+                    self.#field = serde_json::from_value(serde_json::Value::String(#message.to_string()))
+                        .unwrap_or_default();
+
+                    self
+                }
+            });
+        }
+    }
+
+    // Import the format-preserving cipher only when a field uses it
+    let fpe_import = match all_fpe_fields.is_empty() {
+        true => quote::quote! {},
+        false => quote::quote! { use ciphers::FpeExt; }
+    };
+
+    // Create cipher calls, a struct-level backend replaces `CipherExt` with its associated functions
+    let (cipher_encrypt, cipher_decrypt) = match &cipher {
+        Some(cipher) => (quote::quote!(#cipher::encrypt), quote::quote!(#cipher::decrypt)),
+        None => (
+            quote::quote!(ciphers::CipherExt::encrypt),
+            quote::quote!(ciphers::CipherExt::decrypt)
+        )
+    };
+
+    // Keyed fields call the backend's `encrypt_with_key` and `decrypt_with_key` with their key id
+    let (cipher_encrypt_keyed, cipher_decrypt_keyed) = match &cipher {
+        Some(cipher) => (quote::quote!(#cipher::encrypt_with_key), quote::quote!(#cipher::decrypt_with_key)),
+        None => (quote::quote!(), quote::quote!())
+    };
+    let all_keyed_names = all_keyed_fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+
+    // Create ciphertext envelope, default key ciphertext is tagged with its version and legacy tags decrypt with their backend
+    let version = match &struct_attrs.version {
+        Some(version) if version.value().is_empty() || version.value().contains(':') => return Err(syn::Error::new(
+            version.span(),
+            "version must be a non-empty tag without `:`"
+        )),
+        Some(version) => Some(version.value()),
+        None => None
+    };
+
+    // Only default key ciphertext carries the tag, other fields would never be reported by `needs_rotation`
+    if let (Some(attr), Some(field)) = (
+        &struct_attrs.version,
+        all_keyed_fields.iter().chain(&all_deterministic_fields).chain(&all_fpe_fields).next()
+    ) {
+        return Err(syn::Error::new(
+            attr.span(),
+            format!("version only tags default key fields, `{}` uses key, deterministic or fpe encryption", field)
+        ));
+    }
+
+    let legacies = derive_legacies(struct_attrs.legacy.as_ref(), version.is_some())?;
+    let legacy_tags = legacies.iter().map(|(tag, _)| tag).collect::<Vec<_>>();
+    let legacy_ciphers = legacies.iter().map(|(_, cipher)| cipher).collect::<Vec<_>>();
+
+    let seal = |field: &syn::Ident, encrypted: TS2| match &version {
+        Some(version) => quote::quote! {
+            data.#field = #encrypted;
+
+            if let #nulls::Null::Value(value) = &data.#field {
+                data.#field = #nulls::Null::Value(format!("{}:{}", #version, value));
+            }
+        },
+        None => quote::quote! {
+            data.#field = #encrypted;
+        }
+    };
+    let open = |field: &syn::Ident, decrypt: &dyn Fn(TS2) -> TS2| match &version {
+        Some(version) => {
+            let current = decrypt(quote::quote!(&#nulls::Null::Value(rest.to_string())));
+            let untagged = decrypt(quote::quote!(&data.#field));
+
+            quote::quote! {
+                data.#field = match &data.#field {
+                    #nulls::Null::Value(value) => match value.split_once(':') {
+                        Some((#version, rest)) => #current,
+                        #(
+                            Some((#legacy_tags, rest)) => #legacy_ciphers::decrypt(&#nulls::Null::Value(rest.to_string())),
+                        )*
+                        _ => #untagged
+                    },
+                    _ => #untagged
+                };
+            }
+        },
+        None => {
+            let decrypted = decrypt(quote::quote!(&data.#field));
+
+            quote::quote! {
+                data.#field = #decrypted;
+            }
+        }
+    };
+
+    let all_cipher_encrypts = all_cipher_fields.iter()
+        .map(|field| seal(field, quote::quote!(#cipher_encrypt(&data.#field))))
+        .collect::<Vec<_>>();
+    let all_cipher_decrypts = all_cipher_fields.iter()
+        .map(|field| open(field, &|value| quote::quote!(#cipher_decrypt(#value))))
+        .collect::<Vec<_>>();
+    let (all_cipher_encrypts_async, all_cipher_decrypts_async) = match &cipher {
+        Some(cipher) => (
+            all_cipher_fields.iter()
+                .map(|field| seal(field, quote::quote! {
+                    #cipher::encrypt_async(&data.#field)
+                        .await
+                        .map_err(|e| #responder::to(e.to_string()))?
+                }))
+                .collect::<Vec<_>>(),
+            all_cipher_fields.iter()
+                .map(|field| open(field, &|value| quote::quote! {
+                    #cipher::decrypt_async(#value)
+                        .await
+                        .map_err(|e| #responder::to(e.to_string()))?
+                }))
+                .collect::<Vec<_>>()
+        ),
+        None => (vec![], vec![])
+    };
+
+    // Rotation re-tags the ciphertext, stripping whichever known tag it carried
+    let known_tags = version.iter().chain(legacies.iter().map(|(tag, _)| tag)).collect::<Vec<_>>();
+    let untag = match known_tags.is_empty() {
+        true => quote::quote! {},
+        false => quote::quote! {
+            let value = match value.split_once(':') {
+                #(Some((#known_tags, rest)) => rest,)*
+                _ => value.as_str()
+            };
+        }
+    };
+    let retag = match &version {
+        Some(version) => quote::quote!(format!("{}:{}", #version, new.encrypt(&old.decrypt(value)))),
+        None => quote::quote!(new.encrypt(&old.decrypt(value)))
+    };
+    let needs_rotation = match &version {
+        Some(version) => {
+            let prefix = format!("{}:", version);
+
+            quote::quote! {
+                /// The version tag prefixed to ciphertext of fields using the default key.
+                pub const CIPHER_VERSION: &'static str = #version;
+
+                /// Checks whether any field using the default key holds ciphertext of another version.
+                ///
+                /// # Returns
+                /// - `true` if a field is untagged or tagged with a legacy version.
+                /// - `false` if every field is on the current version.
+                pub fn needs_rotation(&self) -> bool {
+                    false #(|| matches!(&self.#all_cipher_fields, #nulls::Null::Value(value) if !value.starts_with(#prefix)))*
+                }
+            }
+        },
+        None => quote::quote! {}
+    };
+
+    // Create integrity signature over the ciphertexts, kept in the plain `signature` field
+    let (sign, verify, verified, parse_verify, integrity) = match struct_attrs.integrity.is_set() {
+        true => {
+            let Some(cipher) = &cipher else {
+                return Err(syn::Error::new(
+                    node.span(),
+                    format!("integrity on {} requires a cipher backend, set #[encryption(cipher = \"...\")]", node)
+                ));
+            };
+
+            // Hashes and nested documents are signed alongside ciphertexts, a swapped one would otherwise verify
+            let all_signed_fields = all_attributed_fields.iter()
+                .chain(&all_hashed_fields)
+                .chain(all_nested.iter().map(|(field, _)| field))
+                .collect::<Vec<_>>();
+
+            if !all_column_fields.iter().any(|f| f == "signature") || all_signed_fields.iter().any(|f| *f == "signature") {
+                return Err(syn::Error::new(
+                    node.span(),
+                    format!("integrity on {} requires an unencrypted `signature: Null<String>` field", node)
+                ));
+            }
+
+            let all_signed_names = all_signed_fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+            let tampered = format!("{} failed integrity verification", node);
+
+            (
+                quote::quote! {
+                    data.signature = #nulls::Null::Value(#cipher::sign(&data.signature_payload()));
+                },
+                quote::quote! {
+                    self.verify_signature()?;
+                },
+                quote::quote! {
+                    if self.verify_signature().is_err() {
+                        return self.clone();
+                    }
+                },
+                quote::quote! {
+                    d.verify_signature()?;
+                },
+                quote::quote! {
+                    fn signature_payload(&self) -> String {
+                        serde_json::json!({ #(#all_signed_names: &self.#all_signed_fields),* }).to_string()
+                    }
+
+                    /// Verifies the signature computed over the ciphertexts during `encrypt()`.
+                    ///
+                    /// # Returns
+                    /// - `Ok(())` if the ciphertexts are unchanged since they were signed.
+                    /// - `Err(responder::Error)` if the document was tampered with or never signed.
+                    pub fn verify_signature(&self) -> #responder::Result<()> {
+                        match &self.signature {
+                            #nulls::Null::Value(signature) if #cipher::verify(&self.signature_payload(), signature) => Ok(()),
+                            _ => Err(#responder::to(#tampered))
+                        }
+                    }
+                }
+            )
+        },
+        false => (quote::quote! {}, quote::quote! {}, quote::quote! {}, quote::quote! {}, quote::quote! {})
+    };
+
+    // Create recursion into nested fields for each method
+    let nested = |call: TS2| all_nested.iter()
+        .map(|(field, ty)| derive_nested(field, ty, &call, &nulls))
+        .collect::<Vec<_>>();
+    let nested_encrypt = nested(quote::quote!(encrypt()));
+    let nested_decrypt = nested(quote::quote!(decrypt_with(context)));
+    let nested_re_encrypt = nested(quote::quote!(re_encrypt(old, new)));
+    let nested_re_encrypt_key = nested(quote::quote!(re_encrypt_key(key, old, new)));
+    let nested_masked = nested(quote::quote!(to_masked()));
+    let nested_encrypt_async = nested(quote::quote!(encrypt_async().await?));
+    let nested_decrypt_async = nested(quote::quote!(decrypt_with_async(context).await?));
+
+    // Create decryption of every encrypted field, failures are told apart by a value decrypting to none or empty
+    let decrypt_fields = quote::quote! {
+        #(#all_cipher_decrypts)*
+
+        #(
+            data.#all_keyed_fields = #cipher_decrypt_keyed(&data.#all_keyed_fields, #all_key_ids);
+        )*
+
+        #(
+            data.#all_deterministic_fields = #deterministic_decrypt(&data.#all_deterministic_fields);
+        )*
+
+        #(
+            data.#all_fpe_fields = data.#all_fpe_fields.decrypt_fpe();
+        )*
+    };
+    let all_decrypt_errors = all_attributed_fields.iter()
+        .map(|f| format!("Unable to decrypt {} field `{}`", node, f))
+        .collect::<Vec<_>>();
+    let nested_try_decrypt = nested(quote::quote!(try_decrypt_with(context)?));
+
+    // Create decrypt audit hook
+    let node_name = node.to_string();
+    let all_attributed_names = all_attributed_fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    let audit = match (compose.audit, &struct_attrs.audit) {
+        (true, Some(sink)) => quote::quote! {
+            #sink(#node_name, &[#(#all_attributed_names),*], context);
+        },
+        _ => quote::quote! {}
+    };
+
+    // Create the touches of copied fields, tracked models only update the fields marked touched
+    let touch_copied = |data: TS2, source: TS2| match is_tracked {
+        true => {
+            let unset = all_column_kinds.iter()
+                .zip(&all_column_fields)
+                .map(|(kind, field)| kind.undefined(quote::quote!(#source.#field)));
+
+            quote::quote! {
+                #(
+                    if !#unset {
+                        #data.touched.touch(stringify!(#all_column_fields));
+                    }
+                )*
+            }
+        },
+        false => quote::quote!()
+    };
+    let touch_mutated = touch_copied(quote::quote!(self), quote::quote!(form));
+    let touch_converted = touch_copied(quote::quote!(data), quote::quote!(value));
+
+    // Create emptiness check, left to the composing derive when it has one
+    let is_empty = match compose.skip.contains(&"is_empty") {
+        true => quote::quote!(),
+        false => quote::quote! {
+            /// Checks if the current instance is equivalent to the default value of its type.
+            ///
+            /// # Returns
+            /// - `true` if the instance is equal to the default value.
+            /// - `false` otherwise.
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+        }
+    };
+
+    // Create field getters, left to the composing derive when it has them
+    let getters = match compose.skip.contains(&"getters") {
+        true => quote::quote!(),
+        false => quote::quote! {
+            #(
+                pub fn #all_column_fields(&self) -> #all_column_inner_types {
+                    self.#all_column_fields.clone().take().unwrap_or_default()
+                }
+            )*
+        }
+    };
+
+    // Cipher Related
+    //________________________________________________________
+    token.extend(quote::quote! {
+        #(
+            pub fn #all_attributed_fields() -> #all_attributed_inner_types {
+                crate::clone().#all_attributed_fields.clone().take().unwrap_or_default()
+            }
+        )*
+
+        impl #node {
+            /// Key ids of the fields encrypted with a named key, as `(field, key)` pairs.
+            pub const KEY_IDS: &'static [(&'static str, &'static str)] = &[#((#all_keyed_names, #all_key_ids)),*];
+
+            /// Returns the key id a field was encrypted with.
+            ///
+            /// # Parameters
+            /// - `field`: The field name as declared on the struct.
+            ///
+            /// # Returns
+            /// - `Some(key)` for fields declared with `#[encryption(key)]`.
+            /// - `None` for fields using the default key or not encrypted at all.
+            pub fn key_id(field: &str) -> Option<&'static str> {
+                Self::KEY_IDS.iter().find(|(name, _)| *name == field).map(|(_, key)| *key)
+            }
+
+            /// Updates the current instance with the values from another instance of the same type.
+            ///
+            /// # Parameters
+            /// - `form`: A reference to another instance of `Self` whose values will be copied.
+            ///
+            /// # Returns
+            /// - A mutable reference to the updated instance (`self`).
+            pub fn mutate(&mut self, form: &Self) -> &mut Self {
+                #(
+                    self.#all_column_fields = form.#all_column_fields.clone();
+                )*
+
+                #touch_mutated
+
+                self
+            }
+
+            /// Encrypts sensitive fields of the current instance using the configured cipher,
+            /// the `CipherExt` trait unless `#[encryption(cipher)]` names another backend.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with encrypted fields.
+            pub fn encrypt(&self) -> Self {
+                #fpe_import
+
+                let mut data = self.clone();
+
+                #(#all_cipher_encrypts)*
+
+                #(
+                    data.#all_keyed_fields = #cipher_encrypt_keyed(&data.#all_keyed_fields, #all_key_ids);
+                )*
+
+                #(
+                    data.#all_deterministic_fields = #deterministic_encrypt(&data.#all_deterministic_fields);
+                )*
+
+                #(
+                    data.#all_fpe_fields = data.#all_fpe_fields.encrypt_fpe();
+                )*
+
+                #(#nested_encrypt)*
+
+                #sign
+
+                data
+            }
+
+            /// Decrypts sensitive fields of the current instance using the configured cipher.
+            ///
+            /// With integrity enabled, a document whose signature does not verify is returned undecrypted,
+            /// `try_decrypt` reports why.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with decrypted fields.
+            pub fn decrypt(&self) -> Self {
+                self.decrypt_with("")
+            }
+
+            /// Decrypts sensitive fields, passing `context` to the audit sink when auditing is enabled.
+            ///
+            /// # Parameters
+            /// - `context`: Caller-supplied context recorded with the access, such as a request id.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with decrypted fields.
+            #[allow(unused_variables)]
+            pub fn decrypt_with(&self, context: &str) -> Self {
+                #fpe_import
+
+                #verified
+
+                #audit
+
+                let mut data = self.clone();
+
+                #decrypt_fields
+
+                #(#nested_decrypt)*
+
+                data
+            }
+
+            /// Decrypts sensitive fields, reporting the first field that fails to decrypt.
+            ///
+            /// # Returns
+            /// - `Ok(Self)` with decrypted fields.
+            /// - `Err(responder::Error)` naming the field if it held a value but decrypted to none or an empty one.
+            /// - `Err(responder::Error)` if integrity is enabled and the signature does not verify.
+            pub fn try_decrypt(&self) -> #responder::Result<Self> {
+                self.try_decrypt_with("")
+            }
+
+            /// Decrypts sensitive fields like `try_decrypt`, passing `context` to the audit sink when auditing is enabled.
+            ///
+            /// # Parameters
+            /// - `context`: Caller-supplied context recorded with the access, such as a request id.
+            ///
+            /// # Returns
+            /// - `Ok(Self)` with decrypted fields.
+            /// - `Err(responder::Error)` naming the field that failed to decrypt.
+            #[allow(unused_variables)]
+            pub fn try_decrypt_with(&self, context: &str) -> #responder::Result<Self> {
+                #fpe_import
+
+                #verify
+
+                #audit
+
+                let mut data = self.clone();
+
+                #decrypt_fields
+
+                #(
+                    if matches!(&self.#all_attributed_fields, #nulls::Null::Value(_))
+                        && !matches!(&data.#all_attributed_fields, #nulls::Null::Value(value) if *value != <#all_attributed_inner_types>::default())
+                    {
+                        return Err(#responder::to(#all_decrypt_errors));
+                    }
+                )*
+
+                #(#nested_try_decrypt)*
+
+                Ok(data)
+            }
+
+            /// Re-encrypts the fields using the default key, decrypting with `old` and encrypting with `new`.
+            ///
+            /// Keyed fields are rotated with `re_encrypt_key`, format-preserving and deterministic fields are left as is.
+            ///
+            /// # Parameters
+            /// - `old`: The cipher holding the key the fields are currently encrypted with.
+            /// - `new`: The cipher holding the key to encrypt the fields with.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with the fields encrypted under the new key.
+            pub fn re_encrypt(&self, old: &impl derives::Cipher, new: &impl derives::Cipher) -> Self {
+                let mut data = self.clone();
+
+                #(
+                    if let #nulls::Null::Value(value) = &self.#all_cipher_fields {
+                        #untag
+                        data.#all_cipher_fields = #nulls::Null::Value(#retag);
+                    }
+                )*
+
+                #(#nested_re_encrypt)*
+
+                #sign
+
+                data
+            }
+
+            /// Re-encrypts the fields declared with `#[encryption(key)]` naming `key`.
+            ///
+            /// # Parameters
+            /// - `key`: The key id being rotated.
+            /// - `old`: The cipher holding the key the fields are currently encrypted with.
+            /// - `new`: The cipher holding the key to encrypt the fields with.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with the fields of that key encrypted under the new key.
+            #[allow(unused_variables)]
+            pub fn re_encrypt_key(&self, key: &str, old: &impl derives::Cipher, new: &impl derives::Cipher) -> Self {
+                let mut data = self.clone();
+
+                #(
+                    if let (#all_key_ids, #nulls::Null::Value(value)) = (key, &self.#all_keyed_fields) {
+                        data.#all_keyed_fields = #nulls::Null::Value(new.encrypt(&old.decrypt(value)));
+                    }
+                )*
+
+                #(#nested_re_encrypt_key)*
+
+                #sign
+
+                data
+            }
+
+            #is_empty
+
+            #getters
+
+            #(#hashers)*
+
+            #(#lookups)*
+
+            #needs_rotation
+
+            #integrity
+
+            /// Returns a copy with sensitive fields partially or fully masked, for logs and support tooling.
+            ///
+            /// Masks apply to the values as they are, so call it on decrypted data.
+            ///
+            /// # Returns
+            /// - A new instance of `Self` with masked fields.
+            pub fn to_masked(&self) -> Self {
+                let mut data = self.clone();
+
+                #(#maskers)*
+
+                #(#nested_masked)*
+
+                data
+            }
+        }
+    });
+
+    // Parsers Related, reading the document from a jsonb column
+    //________________________________________________________
+    if !compose.skip.contains(&"parsers") {
+        token.extend(quote::quote! {
+            pub mod parsers {
+                use sqlx::Row;
+                use super::#node;
+
+                /// Parses a PostgreSQL row (`PgRow`) into an instance of `Self`.
+                ///
+                /// # Parameters
+                /// - `row`: A reference to a `PgRow` containing the data to be parsed.
+                ///
+                /// # Returns
+                /// - An instance of `Self` populated with the values from the `PgRow`.
+                ///   If a field cannot be retrieved, it will use the `Null` type as a fallback.
+                /// - `Err(responder::Error)` if integrity is enabled and the signature does not verify.
+                pub fn parse<T>(value: &sqlx::postgres::PgRow, column: T) -> #responder::Result<#node>
+                where
+                    T: ToString
+                {
+                    if let Ok(d) = value.try_get::<#node, &str>(&column.to_string()) {
+                        #parse_verify
+
+                        return Ok(d.decrypt());
+                    }
+
+                    Err(#responder::to(#error))
+                }
+
+                /// Parses a PostgreSQL row like `parse`, reporting fields that fail to decrypt.
+                ///
+                /// # Parameters
+                /// - `value`: A reference to a `PgRow` containing the data to be parsed.
+                /// - `column`: The jsonb column holding the encrypted document.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` with decrypted fields.
+                /// - `Err(responder::Error)` if the column cannot be read or a field fails to decrypt.
+                pub fn try_parse<T>(value: &sqlx::postgres::PgRow, column: T) -> #responder::Result<#node>
+                where
+                    T: ToString
+                {
+                    match value.try_get::<#node, &str>(&column.to_string()) {
+                        Ok(d) => d.try_decrypt(),
+                        Err(_) => Err(#responder::to(#error))
+                    }
+                }
+
+                /// Converts a SQLx query result into a `responder::Result<Self>`.
+                ///
+                /// # Parameters
+                /// - `row`: A `Result` containing a `PgRow` or an error.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` if the row is successfully parsed and is not empty.
+                /// - `Err(responder::to(#error))` if the row is empty or the query fails.
+                pub fn result<T>(value: sqlx::Result<sqlx::postgres::PgRow>, column: T) -> #responder::Result<#node>
+                where
+                    T: ToString
+                {
+                    if let Ok(d) = value {
+                        return parse(&d, column);
+                    }
+
+                    Err(#responder::to(#error))
+                }
+            }
+        });
+    }
+
+    // Sqlx Related, storing the document as jsonb
+    //________________________________________________________
+    if !compose.skip.contains(&"sqlx") {
+        token.extend(quote::quote! {
+            impl sqlx::Type<sqlx::Postgres> for #node {
+                fn type_info() -> sqlx::postgres::PgTypeInfo {
+                    <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::type_info()
+                }
+            }
+
+            impl<'q> sqlx::Encode<'q, sqlx::Postgres> for #node {
+                fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> Result<sqlx::encode::IsNull, Box<dyn serde::ser::StdError + Send + Sync + 'static>> {
+                    <sqlx::types::Json<&Self> as sqlx::Encode<'q, sqlx::Postgres>>::encode(sqlx::types::Json(self), buf)
+                }
+            }
+
+            impl<'r> sqlx::Decode<'r, sqlx::Postgres> for #node {
+                fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                    let bytes = value.as_str()?
+                        .strip_prefix('\u{1}')
+                        .unwrap_or(value.as_str()?);
+
+                    Ok(serde_json::from_str(bytes)?)
+                }
+            }
+        });
+    }
+
+    // Async Cipher Related
+    //________________________________________________________
+    if struct_attrs.is_async.is_set() {
+        let Some(cipher) = &cipher else {
+            return Err(syn::Error::new(
+                node.span(),
+                format!("async on {} requires a cipher backend, set #[encryption(cipher = \"...\")]", node)
+            ));
+        };
+
+        token.extend(quote::quote! {
+            impl #node {
+                /// Encrypts sensitive fields through the cipher backend's async functions, e.g. a remote KMS.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` with encrypted fields.
+                /// - `Err(responder::Error)` if the backend fails.
+                pub async fn encrypt_async(&self) -> #responder::Result<Self> {
+                    #fpe_import
+
+                    let mut data = self.clone();
+
+                    #(#all_cipher_encrypts_async)*
+
+                    #(
+                        data.#all_keyed_fields = #cipher::encrypt_with_key_async(&data.#all_keyed_fields, #all_key_ids)
+                            .await
+                            .map_err(|e| #responder::to(e.to_string()))?;
+                    )*
+
+                    #(
+                        data.#all_deterministic_fields = #cipher::encrypt_deterministic_async(&data.#all_deterministic_fields)
+                            .await
+                            .map_err(|e| #responder::to(e.to_string()))?;
+                    )*
+
+                    #(
+                        data.#all_fpe_fields = data.#all_fpe_fields.encrypt_fpe();
+                    )*
+
+                    #(#nested_encrypt_async)*
+
+                    #sign
+
+                    Ok(data)
+                }
+
+                /// Decrypts sensitive fields through the cipher backend's async functions.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` with decrypted fields.
+                /// - `Err(responder::Error)` if the backend fails.
+                pub async fn decrypt_async(&self) -> #responder::Result<Self> {
+                    self.decrypt_with_async("").await
+                }
+
+                /// Decrypts sensitive fields asynchronously, passing `context` to the audit sink when auditing is enabled.
+                ///
+                /// # Parameters
+                /// - `context`: Caller-supplied context recorded with the access, such as a request id.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` with decrypted fields.
+                /// - `Err(responder::Error)` if the backend fails.
+                #[allow(unused_variables)]
+                pub async fn decrypt_with_async(&self, context: &str) -> #responder::Result<Self> {
+                    #fpe_import
+
+                    #verify
+
+                    #audit
+
+                    let mut data = self.clone();
+
+                    #(#all_cipher_decrypts_async)*
+
+                    #(
+                        data.#all_keyed_fields = #cipher::decrypt_with_key_async(&data.#all_keyed_fields, #all_key_ids)
+                            .await
+                            .map_err(|e| #responder::to(e.to_string()))?;
+                    )*
+
+                    #(
+                        data.#all_deterministic_fields = #cipher::decrypt_deterministic_async(&data.#all_deterministic_fields)
+                            .await
+                            .map_err(|e| #responder::to(e.to_string()))?;
+                    )*
+
+                    #(
+                        data.#all_fpe_fields = data.#all_fpe_fields.decrypt_fpe();
+                    )*
+
+                    #(#nested_decrypt_async)*
+
+                    Ok(data)
+                }
+            }
+        });
+    }
+
+    // Zeroize Related, wiping encrypted fields that hold plaintext once decrypted
+    //________________________________________________________
+    if struct_attrs.zeroize.is_set() {
+        let all_wipes = all_attributed_fields.iter()
+            .zip(&all_attributed_kinds)
+            .map(|(field, kind)| match kind {
+                crate::FieldKind::Null(_) => quote::quote! {
+                    if let #nulls::Null::Value(value) = &mut self.#field {
+                        zeroize::Zeroize::zeroize(value);
+                    }
+                },
+                crate::FieldKind::Option(_) => quote::quote! {
+                    if let Some(value) = &mut self.#field {
+                        zeroize::Zeroize::zeroize(value);
+                    }
+                },
+                _ => quote::quote! {
+                    zeroize::Zeroize::zeroize(&mut self.#field);
+                }
+            });
+
+        token.extend(quote::quote! {
+            impl zeroize::Zeroize for #node {
+                fn zeroize(&mut self) {
+                    #(#all_wipes)*
+                }
+            }
+
+            impl Drop for #node {
+                fn drop(&mut self) {
+                    zeroize::Zeroize::zeroize(self);
+                }
+            }
+        });
+    }
+
+    // Responder Related
+    //________________________________________________________
+    if compose.actix && !compose.skip.contains(&"responder") {
+        token.extend(quote::quote! {
+            impl actix_web::Responder for #node {
+                type Body = actix_web::body::BoxBody;
+
+                fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+                    actix_web::HttpResponse::Ok().json(serde_json::json!({
+                        "code": 200,
+                        "data": self
+                    }))
+                }
+            }
+        });
+    }
+
+    // Form Related
+    //________________________________________________________
+    token.extend(quote::quote! {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        #[derive(Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct #node_form {
+            #(#all_form_struct_fields,)*
+        }
+
+        impl #node_form {
+            /// Checks if the current instance is equivalent to the default value of its type.
+            ///
+            /// # Returns
+            /// - `true` if the instance is equal to the default value.
+            /// - `false` otherwise.
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+
+            /// Sanitizes the current instance by applying a series of sanitizer functions.
+            ///
+            /// # Returns
+            /// - A sanitized copy of the current instance.
+            ///
+            /// # Implementation
+            /// - Each sanitizer in the `#sanitizers` sequence is applied to the cloned instance.
+            pub fn sanitize(&self) -> Self {
+                let mut data = self.clone();
+
+                #(#sanitizers)*
+
+                data
+            }
+
+            #(#all_form_props)*
+        }
+
+        impl From<#node> for #node_form {
+            fn from(value: #node) -> Self {
+                let mut data = Self::default();
+
+                #(
+                    data.#all_column_fields = value.#all_column_fields.clone();
+                )*
+
+                data
+            }
+        }
+
+        impl From<#node_form> for #node {
+            fn from(value: #node_form) -> Self {
+                let mut data = Self::default();
+
+                #(
+                    data.#all_column_fields = value.#all_column_fields.clone();
+                )*
+
+                #touch_converted
+
+                data
+            }
+        }
+    });
+
+    // Error Related
+    // ________________________________________________________
+    token.extend(quote::quote! {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        #[derive(Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct #node_error {
+            #(#all_error_struct_fields,)*
+        }
+
+       impl #node_error {
+            /// Checks if the current instance is equivalent to the default value of its type.
+            ///
+            /// # Returns
+            /// - `true` if the instance is equal to the default value of `Self`.
+            /// - `false` otherwise.
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+
+            /// Validates the current instance.
+            ///
+            /// This method checks whether the instance is empty (equivalent to the default value).
+            /// If it is empty, the method returns `Ok(())`. Otherwise, it returns an error.
+            ///
+            /// # Returns
+            /// - `Ok(())` if the instance is empty (i.e., equal to the default value).
+            /// - `Err(responder::to(self))` if the instance is not empty, returning an error based on `self`.
+            pub fn validate(&self) -> #responder::Result<()> {
+                if self.is_empty() {
+                    return Ok(())
+                }
+
+                Err(#responder::to(self))
+            }
+
+            #(#rejections)*
+        }
+
+        impl #node_form {
+            /// Converts the current instance to the associated error type.
+            ///
+            /// Fields declaring `#[encryption(error_message)]` are required, so each one left
+            /// unset, null or empty is rejected with its message.
+            ///
+            /// # Returns
+            /// - An error holding the message of every rejected field, empty when none was.
+            pub fn to_error(&self) -> #node_error {
+                #[allow(unused_mut)]
+                let mut error = #node_error::default();
+
+                #(#validations)*
+
+                error
+            }
+        }
+
+        impl #node {
+            /// Builds an encrypted instance from a submitted form.
+            ///
+            /// Runs sanitization, validation, conversion and encryption in that order, so
+            /// handlers cannot skip a step or encrypt values that were never sanitized.
+            ///
+            /// # Parameters
+            /// - `form`: The submitted form.
+            ///
+            /// # Returns
+            /// - `Ok(Self)` with sensitive fields encrypted.
+            /// - `Err(responder::to(#node_error))` if a field declaring `error_message` is left empty once sanitized.
+            pub fn intake(form: #node_form) -> #responder::Result<Self> {
+                let form = form.sanitize();
+                form.to_error().validate()?;
+
+                Ok(Self::from(form).encrypt())
+            }
+        }
+    });
+
+    // Create To implementations, the model's left to the composing derive when it has one
+    token.extend(quote::quote!{
+        impl derives::To for #node_form {}
+    });
+
+    if !compose.skip.contains(&"to") {
+        token.extend(quote::quote!{
+            impl derives::To for #node {}
+        });
+    }
+
+    // Create generator constants
+    token.extend(crate::derive_generator_consts(ast, "Encryption", compose.version, &[
+        ("actix", compose.actix),
+        ("audit", compose.audit),
+    ]));
+
+    // Return the new token
+    Ok(token)
+}
\ No newline at end of file
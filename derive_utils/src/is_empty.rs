@@ -0,0 +1,42 @@
+//! Shared generation of the `IsEmpty` derive, standalone or composed by `Model`.
+use crate::Compose;
+use proc_macro2::TokenStream as TS2;
+use syn::DeriveInput;
+
+/// Expands the `IsEmpty` derive, leaving out the blocks another composed derive owns.
+pub fn derive_is_empty(ast: &DeriveInput, compose: &Compose) -> syn::Result<TS2> {
+    let generator = crate::derive_generator_consts(ast, "IsEmpty", compose.version, &[]);
+    let node = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    // Create emptiness check, left to the composing derive when it has one
+    let is_empty = match compose.skip.contains(&"is_empty") {
+        true => quote::quote!(),
+        false => quote::quote! {
+            /// Checks if the current instance is equivalent to the default value of its type.
+            ///
+            /// # Returns
+            /// - `bool` - `true` if `self` is equal to the default value, otherwise `false`.
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+        }
+    };
+
+    Ok(quote::quote! {
+        impl #impl_generics #node #ty_generics #where_clause {
+            #is_empty
+
+            /// Checks if the given value is empty, for use with
+            /// `#[serde(skip_serializing_if = "Node::is_empty_ref")]`.
+            ///
+            /// # Returns
+            /// - `bool` - `true` if `value` is equal to the default value, otherwise `false`.
+            pub fn is_empty_ref(value: &Self) -> bool {
+                value.is_empty()
+            }
+        }
+
+        #generator
+    })
+}
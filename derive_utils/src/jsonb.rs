@@ -0,0 +1,264 @@
+//! Shared generation of the `Jsonb` derive, standalone or composed by `Model`.
+use crate::Compose;
+use proc_macro2::TokenStream as TS2;
+use syn::{DeriveInput, LitInt, LitStr};
+
+crate::attr_spec! {
+    #[deluxe(attributes(jsonb))]
+    pub struct JsonbAttrs {
+        max_bytes: Option<LitInt>,
+        on_overflow: Option<LitStr>,
+    }
+}
+
+/// Expands the `Jsonb` derive, leaving out the blocks another composed derive owns.
+pub fn derive_jsonb(ast: &DeriveInput, compose: &Compose) -> syn::Result<TS2> {
+    // Validate attributes against their registered grammar
+    crate::derive_validate_attrs::<JsonbAttrs>(&ast.attrs)?;
+
+    let attrs = crate::derive_struct_attrs::<JsonbAttrs>(ast);
+    let generator = crate::derive_generator_consts(ast, "Jsonb", compose.version, &[]);
+    let node = &ast.ident;
+    let responder = compose.responder();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let encode_generics = crate::derive_merge_generics(&ast.generics, syn::parse_quote!('q));
+    let decode_generics = crate::derive_merge_generics(&ast.generics, syn::parse_quote!('r));
+    let error = format!("Unable to parse {} jsonb object", node);
+    let overflow = format!("{} jsonb object exceeds the maximum size of {{}} bytes", node);
+
+    // Create size guard
+    //____________________________________________________________
+    let max_bytes = match &attrs.max_bytes {
+        Some(lit) => {
+            let max_bytes = lit.base10_parse::<usize>()?;
+            quote::quote!(Some(#max_bytes))
+        },
+        None => quote::quote!(None),
+    };
+
+    let truncate = match attrs.on_overflow.as_ref().map(|lit| (lit.value(), lit.span())) {
+        None => false,
+        Some((policy, _)) if policy == "error" => false,
+        Some((policy, _)) if policy == "truncate_fields" => true,
+        Some((policy, span)) => {
+            return Err(syn::Error::new(span, format!("Unknown on_overflow policy `{}`, expected `error` or `truncate_fields`", policy)));
+        }
+    };
+
+    // Create overflow guard, truncating through a private helper so it stays out of the public surface
+    let guard = match truncate {
+        true => quote::quote! {
+            fn truncate_longest(value: &mut serde_json::Value) -> bool {
+                fn longest(value: &mut serde_json::Value) -> Option<&mut String> {
+                    match value {
+                        serde_json::Value::String(s) => Some(s).filter(|s| !s.is_empty()),
+                        serde_json::Value::Array(values) => values.iter_mut()
+                            .filter_map(longest)
+                            .max_by_key(|s| s.len()),
+                        serde_json::Value::Object(map) => map.values_mut()
+                            .filter_map(longest)
+                            .max_by_key(|s| s.len()),
+                        _ => None,
+                    }
+                }
+
+                match longest(value) {
+                    Some(s) => {
+                        let mut end = s.len() / 2;
+                        while !s.is_char_boundary(end) {
+                            end -= 1;
+                        }
+
+                        s.truncate(end);
+
+                        true
+                    },
+                    None => false,
+                }
+            }
+
+            let mut value = value;
+
+            while value.to_string().len() > max_bytes {
+                if !truncate_longest(&mut value) {
+                    return Err(format!(#overflow, max_bytes));
+                }
+            }
+        },
+        false => quote::quote! {
+            if value.to_string().len() > max_bytes {
+                return Err(format!(#overflow, max_bytes));
+            }
+        },
+    };
+
+    // Create encoder, serializing straight into the buffer when there is no limit to enforce
+    let encode = match attrs.max_bytes.is_some() {
+        true => quote::quote! {
+            let value = self.to_bounded_json()?;
+
+            <sqlx::types::Json<serde_json::Value> as sqlx::Encode<'q, sqlx::Postgres>>::encode(sqlx::types::Json(value), buf)
+        },
+        false => quote::quote! {
+            <sqlx::types::Json<&Self> as sqlx::Encode<'q, sqlx::Postgres>>::encode(sqlx::types::Json(self), buf)
+        },
+    };
+
+    // Create emptiness check, left to the composing derive when it has one
+    let is_empty = match compose.skip.contains(&"is_empty") {
+        true => quote::quote!(),
+        false => quote::quote! {
+            /// Checks if the current instance is equivalent to the default value of its type.
+            ///
+            /// # Returns
+            /// - `bool` - `true` if `self` is equal to the default value, otherwise `false`.
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+        }
+    };
+
+    let mut token = quote::quote! {
+        impl #impl_generics #node #ty_generics #where_clause {
+            #is_empty
+
+            /// Looks up a nested value in the serialized form using a dot-separated path.
+            ///
+            /// Numeric segments index into arrays, e.g. `"addresses.0.city"`.
+            ///
+            /// # Returns
+            /// - `Some(serde_json::Value)` if every segment of the path resolves, otherwise `None`.
+            pub fn get_path(&self, path: &str) -> Option<serde_json::Value> {
+                let pointer = path
+                    .split('.')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+                    .collect::<String>();
+
+                serde_json::to_value(self).ok()?.pointer(&pointer).cloned()
+            }
+
+            /// Looks up a nested value using a dot-separated path and deserializes it into `T`.
+            ///
+            /// # Returns
+            /// - `Some(T)` if the path resolves and the value deserializes into `T`, otherwise `None`.
+            pub fn get_path_as<T>(&self, path: &str) -> Option<T>
+            where
+                T: serde::de::DeserializeOwned
+            {
+                serde_json::from_value(self.get_path(path)?).ok()
+            }
+
+            /// The maximum serialized size in bytes, if one was configured.
+            pub const MAX_BYTES: Option<usize> = #max_bytes;
+
+            /// Serializes the current instance while enforcing the configured size limit.
+            ///
+            /// When the overflow policy is `truncate_fields`, the longest string values are
+            /// shortened until the payload fits; otherwise an oversized payload is rejected.
+            ///
+            /// # Returns
+            /// - `Ok(serde_json::Value)` if the payload fits within `MAX_BYTES`.
+            /// - `Err(String)` describing the overflow if it does not.
+            pub fn to_bounded_json(&self) -> Result<serde_json::Value, String> {
+                let value = serde_json::to_value(self).map_err(|e| e.to_string())?;
+                let Some(max_bytes) = Self::MAX_BYTES else {
+                    return Ok(value);
+                };
+
+                #guard
+
+                Ok(value)
+            }
+
+            /// Checks the serialized size of the current instance against the configured limit.
+            ///
+            /// # Returns
+            /// - `Ok(())` if the payload fits or can be truncated to fit.
+            /// - `Err(responder::to(String))` if the payload is too large.
+            pub fn check_size(&self) -> #responder::Result<()> {
+                self.to_bounded_json().map(|_| ()).map_err(#responder::to)
+            }
+
+        }
+    };
+
+    // Parsers Related, reading the document from a jsonb column
+    //________________________________________________________
+    if !compose.skip.contains(&"parsers") {
+        token.extend(quote::quote! {
+            pub mod parsers {
+                use sqlx::Row;
+                use super::#node;
+
+                /// Extracts a value of type `Self` from the specified column in the given PostgreSQL row.
+                ///
+                /// # Parameters
+                /// - `value`: A reference to the PostgreSQL row (`PgRow`) from which to extract the value.
+                /// - `column`: The name of the column to extract the value from. It must implement `ToString`.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` if the value is successfully extracted.
+                /// - `Err(responder::to(Self))` if the value cannot be extracted.
+                pub fn row<T>(value: &sqlx::postgres::PgRow, column: T) -> #responder::Result<#node>
+                where
+                    T: ToString
+                {
+                    if let Ok(d) = value.try_get::<#node, &str>(&column.to_string()) {
+                        return Ok(d);
+                    }
+
+                    Err(#responder::to(#error))
+                }
+
+                /// Extracts a value of type `#node` from the specified column in a SQLx result containing a PostgreSQL row.
+                ///
+                /// # Parameters
+                /// - `value`: A SQLx result containing a PostgreSQL row (`PgRow`).
+                /// - `column`: The name of the column to extract the value from. It must implement `ToString`.
+                ///
+                /// # Returns
+                /// - `Ok(Self)` if the row exists and the value is successfully extracted.
+                /// - `Err(responder::to(Self))` if the row does not exist or the value cannot be extracted.
+                pub fn result<T>(value: sqlx::Result<sqlx::postgres::PgRow>, column: T) -> #responder::Result<#node>
+                where
+                    T: ToString
+                {
+                    if let Ok(d) = value {
+                        return row(&d, column);
+                    }
+
+                    Err(#responder::to(#error))
+                }
+            }
+        });
+    }
+
+    token.extend(quote::quote! {
+        impl #impl_generics sqlx::Type<sqlx::Postgres> for #node #ty_generics #where_clause {
+            fn type_info() -> sqlx::postgres::PgTypeInfo {
+                <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::type_info()
+            }
+        }
+
+        impl #encode_generics sqlx::Encode<'q, sqlx::Postgres> for #node #ty_generics #where_clause {
+            fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> Result<sqlx::encode::IsNull, Box<dyn serde::ser::StdError + Send + Sync + 'static>> {
+                #encode
+            }
+        }
+
+        impl #decode_generics sqlx::Decode<'r, sqlx::Postgres> for #node #ty_generics #where_clause {
+            fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                let bytes = value.as_str()?
+                    .strip_prefix('\u{1}')
+                    .unwrap_or(value.as_str()?);
+
+                Ok(serde_json::from_str(bytes)?)
+            }
+        }
+
+        #generator
+    });
+
+    Ok(token)
+}
\ No newline at end of file
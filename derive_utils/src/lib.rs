@@ -1,10 +1,20 @@
-use change_case::snake_case;
+use change_case::{pascal_case, snake_case};
 use deluxe::{extract_attributes, ExtractAttributes};
 use proc_macro2::Ident;
 use regex::Regex;
-use syn::{parse_str, Data, DeriveInput, Field, Fields, Type};
+use std::sync::LazyLock;
+use syn::{parse_str, punctuated::Punctuated, Data, DeriveInput, Field, Fields, Meta, Token, Type};
 
-pub trait Pagination<T> {
+pub mod dialect;
+pub mod encryption;
+pub mod is_empty;
+pub mod jsonb;
+
+// Compiled once and shared across every derive expansion
+static INNER_TYPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[^<]*<(.+)>$").unwrap());
+
+/// A page of records along with the counts a paginated listing reports.
+pub trait Paginated<T> {
     fn page(&self) -> i64;
     fn per_page(&self) -> i64;
     fn filtered_count(&self) -> i64;
@@ -12,6 +22,99 @@ pub trait Pagination<T> {
     fn records(&self) -> Vec<T>;
 }
 
+/// How a shared generator is expanded, standalone by its own derive or composed by `Model`.
+///
+/// Composed generators leave out the blocks named in `skip`, e.g. `is_empty` or `parsers`,
+/// as the derive composing them already generates those for the same type.
+pub struct Compose {
+    /// The version of the derive crate, embedded into generated types.
+    pub version: &'static str,
+    /// Whether the actix `Responder` implementation is generated.
+    pub actix: bool,
+    /// Whether the audit hooks are generated.
+    pub audit: bool,
+    /// The blocks owned by another derive on the same type.
+    pub skip: &'static [&'static str],
+    /// The path of the responder crate, `responder` when unset.
+    pub responder: Option<syn::Path>,
+    /// The path of the nulls crate, `nulls` when unset.
+    pub nulls: Option<syn::Path>,
+}
+
+impl Compose {
+    /// Returns the path generated code reaches the responder crate through.
+    pub fn responder(&self) -> syn::Path {
+        self.responder.clone().unwrap_or_else(|| syn::parse_quote!(responder))
+    }
+
+    /// Returns the path generated code reaches the nulls crate through.
+    pub fn nulls(&self) -> syn::Path {
+        self.nulls.clone().unwrap_or_else(|| syn::parse_quote!(nulls))
+    }
+}
+
+/// The attribute grammar of a derive, registered through [`attr_spec!`].
+pub trait AttrSpec {
+    /// The attribute namespace, e.g. `column` for `#[column(...)]`.
+    const NAMESPACE: &'static str;
+
+    /// Every supported key along with the type it is parsed into.
+    const SUPPORTED_ATTRIBUTES: &'static [(&'static str, &'static str)];
+}
+
+/// Declares a deluxe attribute struct and registers its keys as an [`AttrSpec`].
+///
+/// The struct derives `Default`, `Debug` and `deluxe::ExtractAttributes`, so the
+/// registry used for validation and documentation can never drift from the keys
+/// that are actually parsed. Fields may be renamed with `#[deluxe(rename = key)]`.
+///
+/// # Example
+/// ```rust
+/// use derive_utils::AttrSpec;
+///
+/// derive_utils::attr_spec! {
+///     #[deluxe(attributes(column))]
+///     struct ColumnAttrs {
+///         readonly: deluxe::Flag,
+///         #[deluxe(rename = enum)]
+///         is_enum: deluxe::Flag,
+///     }
+/// }
+///
+/// assert_eq!(ColumnAttrs::NAMESPACE, "column");
+/// assert_eq!(ColumnAttrs::SUPPORTED_ATTRIBUTES[1].0, "enum");
+/// ```
+#[macro_export]
+macro_rules! attr_spec {
+    (
+        #[deluxe(attributes($namespace:ident))]
+        $vis:vis struct $name:ident {
+            $(
+                $(#[deluxe(rename = $key:ident)])?
+                $field_vis:vis $field:ident : $ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        #[derive(Default, Debug, deluxe::ExtractAttributes)]
+        #[deluxe(attributes($namespace))]
+        $vis struct $name {
+            $(
+                $(#[deluxe(rename = $key)])?
+                $field_vis $field: $ty,
+            )*
+        }
+
+        impl $crate::AttrSpec for $name {
+            const NAMESPACE: &'static str = stringify!($namespace);
+            const SUPPORTED_ATTRIBUTES: &'static [(&'static str, &'static str)] = &[
+                $(($crate::attr_spec!(@key $field $($key)?), stringify!($ty)),)*
+            ];
+        }
+    };
+    (@key $field:ident) => { stringify!($field) };
+    (@key $field:ident $key:ident) => { stringify!($key) };
+}
+
 /// Derives all fields of a struct along with their attributes.
 ///
 /// This function extracts all fields from the struct, checking if each field has
@@ -35,8 +138,12 @@ pub trait Pagination<T> {
 ///
 /// # Example
 /// ```rust
+/// use deluxe::ExtractAttributes;
 /// use syn::{parse_quote, DeriveInput};
-/// use quote::format_ident;
+///
+/// #[derive(Default, ExtractAttributes)]
+/// #[deluxe(attributes(my_attribute))]
+/// struct MyAttrs {}
 ///
 /// let ast: DeriveInput = parse_quote! {
 ///     struct MyStruct {
@@ -45,8 +152,10 @@ pub trait Pagination<T> {
 ///         field2: i32,
 ///     }
 /// };
-/// let result = derive_utils::derive_all_fields(&ast, "my_attribute");
+/// let result = derive_utils::derive_all_fields::<&str, MyAttrs>(&ast, "my_attribute");
 /// assert_eq!(result.len(), 2);
+/// assert!(result[0].2);
+/// assert!(!result[1].2);
 /// ```
 pub fn derive_all_fields<T, U>(ast: &DeriveInput, name: T) -> Vec<(Ident, Type, bool, U)>
 where
@@ -56,16 +165,14 @@ where
     let name = name.to_string();
     let mut result = Vec::new();
 
-    if let Data::Struct(s) = &mut ast.data.clone() {
-        for field in s.fields.iter_mut() {
-            if let Some(ident) = &field.clone().ident {
-                if let Ok(attrs) = extract_attributes(&mut field.clone()) {
-                    let has_attribute = derive_is_attributed_field(
-                        ast,
-                        ident.to_string(),
-                        &name
-                    );
+    if let Data::Struct(s) = &ast.data {
+        result.reserve(s.fields.len());
+
+        for field in s.fields.iter() {
+            if let Some(ident) = &field.ident {
+                let has_attribute = field.attrs.iter().any(|attr| attr.path().is_ident(&name));
 
+                if let Ok(attrs) = extract_attributes(&mut field.clone()) {
                     result.push((
                         ident.clone(),
                         field.ty.clone(),
@@ -87,11 +194,195 @@ where
     result
 }
 
+/// Derives all fields of a struct along with their attributes, reporting malformed attributes.
+///
+/// Unlike [`derive_all_fields`], a field whose attribute fails to parse is returned
+/// as an error spanning that attribute instead of being treated as unattributed.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the struct.
+/// - `name`: The name of the attribute to check for.
+///
+/// # Returns
+/// - `Ok(Vec<(Ident, Type, bool, U)>)` as returned by [`derive_all_fields`].
+/// - `Err(syn::Error)` spanning the first malformed attribute.
+///
+/// # Example
+/// ```rust
+/// derive_utils::attr_spec! {
+///     #[deluxe(attributes(column))]
+///     struct ColumnAttrs {
+///         language: Option<syn::LitStr>,
+///     }
+/// }
+///
+/// let ast: syn::DeriveInput = syn::parse_quote! {
+///     struct Post {
+///         #[column(language = 5)]
+///         body: String,
+///     }
+/// };
+/// assert!(derive_utils::derive_try_all_fields::<&str, ColumnAttrs>(&ast, "column").is_err());
+/// ```
+pub fn derive_try_all_fields<T, U>(ast: &DeriveInput, name: T) -> syn::Result<Vec<(Ident, Type, bool, U)>>
+where
+    T: ToString,
+    U: ExtractAttributes<Field>
+{
+    let name = name.to_string();
+    let mut result = Vec::new();
+
+    if let Data::Struct(s) = &ast.data {
+        result.reserve(s.fields.len());
+
+        for field in s.fields.iter() {
+            if let Some(ident) = &field.ident {
+                let has_attribute = field.attrs.iter().any(|attr| attr.path().is_ident(&name));
+                let attrs = extract_attributes(&mut field.clone())?;
+
+                result.push((ident.clone(), field.ty.clone(), has_attribute, attrs));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// How a field type is wrapped, along with the type it wraps.
+///
+/// Wrappers are recognized by the last path segment, so `Null<T>`,
+/// `nulls::Null<T>` and `crate::libs::nulls::Null<T>` all classify the same,
+/// while look-alikes such as `Nullable<T>` are left as plain types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    /// `Null<T>`, the tri-state wrapper used by models and forms.
+    Null(Type),
+    /// `Option<T>`.
+    Option(Type),
+    /// `Vec<T>`, except `Vec<u8>` which is treated as plain bytes.
+    Vec(Type),
+    /// `Json<T>`, e.g. `sqlx::types::Json<T>`.
+    Json(Type),
+    /// Any other type, including `String` and `Vec<u8>`.
+    Plain(Type),
+}
+
+impl FieldKind {
+    /// Returns the wrapped type, or the type itself for plain fields.
+    pub fn inner(&self) -> &Type {
+        match self {
+            Self::Null(ty) | Self::Option(ty) | Self::Vec(ty) | Self::Json(ty) | Self::Plain(ty) => ty,
+        }
+    }
+
+    /// Returns `true` if the field is wrapped in `Null<T>`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null(_))
+    }
+
+    /// Returns the expression checking whether `field` was left unset, plain fields never are.
+    pub fn undefined(&self, field: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Null(_) => quote::quote! { #field.undefined() },
+            Self::Option(_) => quote::quote! { #field.is_none() },
+            _ => quote::quote! { false },
+        }
+    }
+}
+
+/// Classifies a field type by its wrapper.
+///
+/// # Arguments
+/// - `ty`: A reference to the field type.
+///
+/// # Returns
+/// - The `FieldKind` of the type, holding the wrapped type.
+///
+/// # Example
+/// ```rust
+/// use derive_utils::FieldKind;
+///
+/// let ty: syn::Type = syn::parse_quote!(nulls::Null<Vec<String>>);
+/// let kind = derive_utils::derive_field_kind(&ty);
+/// assert_eq!(kind, FieldKind::Null(syn::parse_quote!(Vec<String>)));
+///
+/// let inner = derive_utils::derive_field_kind(kind.inner());
+/// assert_eq!(inner, FieldKind::Vec(syn::parse_quote!(String)));
+///
+/// let ty: syn::Type = syn::parse_quote!(Nullable<String>);
+/// assert!(matches!(derive_utils::derive_field_kind(&ty), FieldKind::Plain(_)));
+/// ```
+pub fn derive_field_kind(ty: &Type) -> FieldKind {
+    let Type::Path(path) = ty else {
+        return FieldKind::Plain(ty.clone());
+    };
+
+    let Some(segment) = path.path.segments.last() else {
+        return FieldKind::Plain(ty.clone());
+    };
+
+    // Wrappers take exactly one type argument
+    let inner = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner)) => inner.clone(),
+            _ => return FieldKind::Plain(ty.clone()),
+        },
+        _ => return FieldKind::Plain(ty.clone()),
+    };
+
+    match segment.ident.to_string().as_str() {
+        "Null" => FieldKind::Null(inner),
+        "Option" => FieldKind::Option(inner),
+        "Vec" if derive_type_matches(&inner, "u8") => FieldKind::Plain(ty.clone()),
+        "Vec" => FieldKind::Vec(inner),
+        "Json" => FieldKind::Json(inner),
+        _ => FieldKind::Plain(ty.clone()),
+    }
+}
+
+/// Ensures the derive input is a struct with named fields.
+///
+/// Column based derives map each field name to a column, so tuple structs,
+/// newtypes, unit structs, enums and unions cannot be derived meaningfully.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the item.
+/// - `derive`: The name of the derive, used in the error message.
+///
+/// # Returns
+/// - `Ok(())` if the item is a struct with named fields.
+/// - `Err(syn::Error)` spanning the offending item otherwise.
+///
+/// # Example
+/// ```rust
+/// let ast: syn::DeriveInput = syn::parse_quote! {
+///     struct Count(pub i64);
+/// };
+/// assert!(derive_utils::derive_named_fields(&ast, "PostgreSQL").is_err());
+/// ```
+pub fn derive_named_fields<T>(ast: &DeriveInput, derive: T) -> syn::Result<()>
+where
+    T: ToString
+{
+    match &ast.data {
+        Data::Struct(s) if matches!(s.fields, Fields::Named(_)) => Ok(()),
+        Data::Struct(s) if !s.fields.is_empty() => Err(syn::Error::new_spanned(
+            &s.fields,
+            format!("{} can only be derived for structs with named fields, tuple structs and newtypes have no column names", derive.to_string())
+        )),
+        _ => Err(syn::Error::new(
+            ast.ident.span(),
+            format!("{} can only be derived for structs with named fields", derive.to_string())
+        )),
+    }
+}
+
 /// Parses the inner type from a type string, if applicable.
 ///
 /// This function takes a reference to a `Type`, converts it to a string representation,
 /// and attempts to parse the inner type if the type is a generic. If the type is not a generic,
-/// it attempts to parse the type directly from the string. If parsing fails, it will panic.
+/// it attempts to parse the type directly from the string. If parsing fails, an error
+/// spanning the type is returned.
 ///
 /// The function uses regular expressions to identify if the type is a generic (e.g., `Option<T>`)
 /// and extracts the inner type (e.g., `T`).
@@ -100,32 +391,29 @@ where
 /// - `ty`: A reference to the type that is to be parsed.
 ///
 /// # Returns
-/// - The inner type if it is a generic type, or the type itself if it's not a generic.
-///
-/// # Panics
-/// - Panics if the type string is invalid and cannot be parsed.
+/// - `Ok(Type)` with the inner type if it is a generic type, or the type itself if it's not a generic.
+/// - `Err(syn::Error)` spanning the type if the type string cannot be parsed.
 ///
 /// # Example
 /// ```rust
 /// let ty: syn::Type = syn::parse_str("Option<i32>").unwrap();
-/// let inner_type = derive_utils::derive_parse_inner_type(&ty);
-/// // inner_type is now `i32`
+/// let inner_type = derive_utils::derive_parse_inner_type(&ty).unwrap();
+/// assert_eq!(derive_utils::derive_type_to_string(&inner_type), "i32");
 /// ```
-pub fn derive_parse_inner_type(ty: &Type) -> Type {
+pub fn derive_parse_inner_type(ty: &Type) -> syn::Result<Type> {
     let input = derive_type_to_string(ty);
 
-    let re = Regex::new(r"^[^<]*<(.+)>$").unwrap();
-    if let Some(captures) = re.captures(&input) {
-        if let Some(captured) = captures.get(1) {
-            if let Ok(ty) = parse_str::<Type>(captured.as_str()) {
-                return ty;
-            }
+    if let Some(captures) = INNER_TYPE.captures(&input) {
+        if let Some(captured) = captures.get(1)
+            && let Ok(ty) = parse_str::<Type>(captured.as_str())
+        {
+            return Ok(ty);
         }
     } else if let Ok(ty) = parse_str::<Type>(&input) {
-        return ty;
+        return Ok(ty);
     }
 
-    panic!("Invalid type string");
+    Err(syn::Error::new_spanned(ty, format!("Unable to parse the inner type of `{}`", input)))
 }
 
 /// Checks if a attributed field in a struct has a specific attribute.
@@ -169,16 +457,16 @@ where
     let field = field.to_string();
     let name = name.to_string();
 
-    if let Data::Struct(data_struct) = &ast.data {
-        if let Fields::Named(fields) = &data_struct.fields {
-            return fields.named.iter().any(|f| {
-                f.ident
-                    .as_ref()
-                    .map(|ident| ident == &field)
-                    .unwrap_or(false)
-                    && f.attrs.iter().any(|attr| attr.path().is_ident(&name))
-            });
-        }
+    if let Data::Struct(data_struct) = &ast.data
+        && let Fields::Named(fields) = &data_struct.fields
+    {
+        return fields.named.iter().any(|f| {
+            f.ident
+                .as_ref()
+                .map(|ident| ident == &field)
+                .unwrap_or(false)
+                && f.attrs.iter().any(|attr| attr.path().is_ident(&name))
+        });
     }
 
     false
@@ -263,6 +551,155 @@ where
     snake_case(&name.to_string()).to_lowercase()
 }
 
+/// Converts a given name to PascalCase.
+///
+/// This function takes a string or any type that can be converted to a string
+/// and converts it to PascalCase, which is the Rust convention for type and
+/// enum variant names generated from field names.
+///
+/// # Type Parameters
+/// - `T`: A type that implements `ToString`, representing the input name to convert.
+///
+/// # Arguments
+/// - `name`: The input name (e.g., "created_at", "email") to convert to PascalCase.
+///
+/// # Returns
+/// - A `String` in PascalCase format (e.g., "CreatedAt", "Email").
+///
+/// # Example
+/// ```rust
+/// assert_eq!(derive_utils::derive_pascal_case("created_at"), "CreatedAt");
+/// assert_eq!(derive_utils::derive_pascal_case("email"), "Email");
+/// ```
+pub fn derive_pascal_case<T>(name: T) -> String
+where
+    T: ToString
+{
+    pascal_case(&name.to_string())
+}
+
+/// Resolves the name serde serializes a field under.
+///
+/// A field level `#[serde(rename = "...")]` wins, followed by the struct level
+/// `#[serde(rename_all = "...")]`. When renames differ per direction, the
+/// `serialize` name is used since it is the one API consumers see.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the struct.
+/// - `field`: The identifier of the field.
+///
+/// # Returns
+/// - The serialized name of the field, or the field name if serde does not rename it.
+///
+/// # Example
+/// ```rust
+/// use syn::{parse_quote, DeriveInput, Ident};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[serde(rename_all = "camelCase")]
+///     struct User {
+///         created_at: String,
+///         #[serde(rename = "emailAddress")]
+///         email: String,
+///     }
+/// };
+///
+/// let created_at: Ident = parse_quote!(created_at);
+/// let email: Ident = parse_quote!(email);
+/// assert_eq!(derive_utils::derive_serde_name(&ast, &created_at), "createdAt");
+/// assert_eq!(derive_utils::derive_serde_name(&ast, &email), "emailAddress");
+/// ```
+pub fn derive_serde_name(ast: &DeriveInput, field: &Ident) -> String {
+    if let Some(rename) = derive_serde_rename(ast, field) {
+        return rename;
+    }
+
+    let name = field.to_string().trim_start_matches("r#").to_string();
+
+    let words = name.split('_').filter(|w| !w.is_empty()).collect::<Vec<&str>>();
+    let capitalize = |word: &&str| {
+        let mut chars = word.chars();
+        chars.next()
+            .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+
+    match derive_serde_value(&ast.attrs, "rename_all").as_deref() {
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => name.to_uppercase(),
+        Some("PascalCase") => words.iter().map(capitalize).collect(),
+        Some("camelCase") => words.iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+            .collect(),
+        Some("kebab-case") => name.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => name.replace('_', "-").to_uppercase(),
+        _ => name,
+    }
+}
+
+/// Reads the explicit `#[serde(rename = "...")]` of a field, ignoring `rename_all`.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the struct.
+/// - `field`: The identifier of the field.
+///
+/// # Returns
+/// - `Some(String)` with the serialized name if the field is renamed, otherwise `None`.
+///
+/// # Example
+/// ```rust
+/// use syn::{parse_quote, DeriveInput, Ident};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[serde(rename_all = "camelCase")]
+///     struct User {
+///         created_at: String,
+///         #[serde(rename(serialize = "emailAddress", deserialize = "email"))]
+///         email: String,
+///     }
+/// };
+///
+/// let created_at: Ident = parse_quote!(created_at);
+/// let email: Ident = parse_quote!(email);
+/// assert_eq!(derive_utils::derive_serde_rename(&ast, &created_at), None);
+/// assert_eq!(derive_utils::derive_serde_rename(&ast, &email), Some("emailAddress".to_string()));
+/// ```
+pub fn derive_serde_rename(ast: &DeriveInput, field: &Ident) -> Option<String> {
+    let Data::Struct(s) = &ast.data else {
+        return None;
+    };
+
+    let field = s.fields.iter().find(|f| f.ident.as_ref() == Some(field))?;
+
+    derive_serde_value(&field.attrs, "rename")
+}
+
+// Reads `key = "..."` or `key(serialize = "...")` from serde attributes
+fn derive_serde_value(attrs: &[syn::Attribute], key: &str) -> Option<String> {
+    let lit = |expr: &syn::Expr| match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+        _ => None,
+    };
+
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .find_map(|meta| match meta {
+            Meta::NameValue(nv) if nv.path.is_ident(key) => lit(&nv.value),
+            Meta::List(list) if list.path.is_ident(key) => list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()?
+                .into_iter()
+                .find_map(|meta| match meta {
+                    Meta::NameValue(nv) if nv.path.is_ident("serialize") => lit(&nv.value),
+                    _ => None,
+                }),
+            _ => None,
+        })
+}
+
 /// Extracts attributes from a struct's derive input.
 ///
 /// This function attempts to extract attributes from the given `DeriveInput`
@@ -315,6 +752,41 @@ where
     T::default()
 }
 
+/// Extracts attributes from a struct's derive input, reporting malformed attributes.
+///
+/// Unlike [`derive_struct_attrs`], a value of the wrong type such as
+/// `#[table(rename = 5)]` is returned as an error spanning the attribute
+/// instead of silently falling back to the defaults.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the struct.
+///
+/// # Returns
+/// - `Ok(T)` with the extracted attributes.
+/// - `Err(syn::Error)` spanning the malformed attribute.
+///
+/// # Example
+/// ```rust
+/// derive_utils::attr_spec! {
+///     #[deluxe(attributes(table))]
+///     struct TableAttrs {
+///         rename: Option<syn::LitStr>,
+///     }
+/// }
+///
+/// let ast: syn::DeriveInput = syn::parse_quote! {
+///     #[table(rename = 5)]
+///     struct User {}
+/// };
+/// assert!(derive_utils::derive_try_struct_attrs::<TableAttrs>(&ast).is_err());
+/// ```
+pub fn derive_try_struct_attrs<T>(ast: &DeriveInput) -> syn::Result<T>
+where
+    T: ExtractAttributes<DeriveInput>
+{
+    extract_attributes(&mut ast.clone())
+}
+
 /// Converts the given `Type` to a string representation.
 ///
 /// This function takes a reference to a `Type` and generates a string that represents
@@ -337,5 +809,356 @@ pub fn derive_type_to_string(ty: &Type) -> String {
     format!("{}", quote::quote! { #ty }).replace(" ", "")
 }
 
+/// Converts a type to a compact string with every path reduced to its last segment.
+///
+/// Users may spell the same type as `String`, `std::string::String` or
+/// `alloc::string::String`. Normalizing paths lets derives compare types by name,
+/// including type arguments, regardless of how they were imported.
+///
+/// # Arguments
+/// - `ty`: A reference to the type to normalize.
+///
+/// # Returns
+/// - A `String` representing the normalized type, with spaces removed.
+///
+/// # Example
+/// ```rust
+/// let ty: syn::Type = syn::parse_quote!(std::vec::Vec<std::string::String>);
+/// assert_eq!(derive_utils::derive_normalize_type(&ty), "Vec<String>");
+///
+/// let ty: syn::Type = syn::parse_quote!(chrono::DateTime<chrono::Utc>);
+/// assert_eq!(derive_utils::derive_normalize_type(&ty), "DateTime<Utc>");
+/// ```
+pub fn derive_normalize_type(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+            Some(segment) => {
+                let args = match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => {
+                        let args = args.args
+                            .iter()
+                            .map(|arg| match arg {
+                                syn::GenericArgument::Type(ty) => derive_normalize_type(ty),
+                                arg => format!("{}", quote::quote! { #arg }).replace(" ", ""),
+                            })
+                            .collect::<Vec<String>>();
+
+                        format!("<{}>", args.join(","))
+                    },
+                    _ => String::new(),
+                };
+
+                format!("{}{}", segment.ident, args)
+            },
+            None => derive_type_to_string(ty),
+        },
+        Type::Reference(reference) => format!(
+            "&{}{}",
+            reference.mutability.map(|_| "mut").unwrap_or_default(),
+            derive_normalize_type(&reference.elem)
+        ),
+        Type::Slice(slice) => format!("[{}]", derive_normalize_type(&slice.elem)),
+        Type::Paren(paren) => derive_normalize_type(&paren.elem),
+        Type::Group(group) => derive_normalize_type(&group.elem),
+        Type::Tuple(tuple) => format!(
+            "({})",
+            tuple.elems.iter().map(derive_normalize_type).collect::<Vec<String>>().join(",")
+        ),
+        _ => derive_type_to_string(ty),
+    }
+}
+
+/// Checks if a type matches the expected type, ignoring how its paths are qualified.
+///
+/// # Arguments
+/// - `ty`: A reference to the type to check.
+/// - `expected`: The expected type, e.g. `"String"` or `"Vec<u8>"`.
+///
+/// # Returns
+/// - `true` if both types are the same once normalized, otherwise `false`.
+///
+/// # Example
+/// ```rust
+/// let ty: syn::Type = syn::parse_quote!(std::string::String);
+/// assert!(derive_utils::derive_type_matches(&ty, "String"));
+///
+/// let ty: syn::Type = syn::parse_quote!(std::vec::Vec<core::primitive::u8>);
+/// assert!(derive_utils::derive_type_matches(&ty, "Vec<u8>"));
+/// assert!(!derive_utils::derive_type_matches(&ty, "Vec<String>"));
+/// ```
+pub fn derive_type_matches<T>(ty: &Type, expected: T) -> bool
+where
+    T: ToString
+{
+    let expected = expected.to_string();
+    let expected = match parse_str::<Type>(&expected) {
+        Ok(expected) => derive_normalize_type(&expected),
+        Err(_) => expected.replace(" ", ""),
+    };
+
+    derive_normalize_type(ty) == expected
+}
+
+/// Validates that every key used in the spec's attribute namespace is supported.
+///
+/// Attribute extraction falls back to defaults when parsing fails, which used to
+/// hide typos such as `#[column(readonyl)]`. This reports them as compile errors
+/// listing the supported keys instead.
+///
+/// # Arguments
+/// - `attrs`: The attributes of a struct or field.
+///
+/// # Returns
+/// - `Ok(())` if every key is supported.
+/// - `Err(syn::Error)` spanning each unsupported key.
+///
+/// # Example
+/// ```rust
+/// derive_utils::attr_spec! {
+///     #[deluxe(attributes(table))]
+///     struct TableAttrs {
+///         rename: Option<syn::LitStr>,
+///     }
+/// }
+///
+/// let ast: syn::DeriveInput = syn::parse_quote! {
+///     #[table(renme = "users")]
+///     struct User {}
+/// };
+/// assert!(derive_utils::derive_validate_attrs::<TableAttrs>(&ast.attrs).is_err());
+/// ```
+pub fn derive_validate_attrs<T>(attrs: &[syn::Attribute]) -> syn::Result<()>
+where
+    T: AttrSpec
+{
+    let mut errors = None::<syn::Error>;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(T::NAMESPACE)) {
+        if !matches!(attr.meta, syn::Meta::List(_)) {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            let key = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+
+            if !T::SUPPORTED_ATTRIBUTES.iter().any(|(supported, _)| *supported == key) {
+                let supported = T::SUPPORTED_ATTRIBUTES
+                    .iter()
+                    .map(|(supported, _)| *supported)
+                    .collect::<Vec<&str>>()
+                    .join(", ");
 
+                let error = meta.error(format!(
+                    "Unknown `{}` attribute `{}`, supported: {}",
+                    T::NAMESPACE, key, supported
+                ));
+
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            }
+
+            // Skip over the value so the remaining keys are still checked
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<proc_macro2::TokenStream>()?;
+            }
 
+            Ok(())
+        })?;
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// Validates the spec's attribute namespace on every field of a struct.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the struct.
+///
+/// # Returns
+/// - `Ok(())` if every field only uses supported keys.
+/// - `Err(syn::Error)` combining the errors of every offending field.
+pub fn derive_validate_fields<T>(ast: &DeriveInput) -> syn::Result<()>
+where
+    T: AttrSpec
+{
+    let mut errors = None::<syn::Error>;
+
+    if let Data::Struct(s) = &ast.data {
+        for field in s.fields.iter() {
+            if let Err(error) = derive_validate_attrs::<T>(&field.attrs) {
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// Renders the attribute grammar of a spec as a markdown table.
+///
+/// # Returns
+/// - A `String` with one row per supported key and the kind of value it takes.
+///
+/// # Example
+/// ```rust
+/// derive_utils::attr_spec! {
+///     #[deluxe(attributes(column))]
+///     struct ColumnAttrs {
+///         readonly: deluxe::Flag,
+///         language: Option<syn::LitStr>,
+///     }
+/// }
+///
+/// let docs = derive_utils::derive_attr_docs::<ColumnAttrs>();
+/// assert!(docs.contains("| `#[column(readonly)]` | flag |"));
+/// assert!(docs.contains("| `#[column(language = ...)]` | string |"));
+/// ```
+pub fn derive_attr_docs<T>() -> String
+where
+    T: AttrSpec
+{
+    let mut docs = String::from("| Attribute | Value |\n| --- | --- |\n");
+
+    for (key, ty) in T::SUPPORTED_ATTRIBUTES {
+        let ty = ty.replace(' ', "");
+        let kind = match ty.as_str() {
+            t if t.ends_with("Flag") => "flag",
+            t if t.contains("LitStr") => "string",
+            t if t.contains("LitInt") => "integer",
+            t if t.contains("LitBool") => "bool",
+            t if t.contains("Path") => "path",
+            t if t.contains("Ident") => "ident",
+            t if t.contains("Type") => "type",
+            t => t,
+        };
+
+        match kind {
+            "flag" => docs.push_str(&format!("| `#[{}({})]` | {} |\n", T::NAMESPACE, key, kind)),
+            _ => docs.push_str(&format!("| `#[{}({} = ...)]` | {} |\n", T::NAMESPACE, key, kind)),
+        }
+    }
+
+    docs
+}
+
+/// Creates the hidden constants recording which generator expanded a derive.
+///
+/// Constants are prefixed with the derive's name so stacking several derives on
+/// one type cannot produce conflicting items.
+///
+/// # Arguments
+/// - `ast`: A reference to the `DeriveInput` of the type.
+/// - `derive`: The name of the derive, e.g. `PostgreSQL`.
+/// - `version`: The version of the derive crate, usually `env!("CARGO_PKG_VERSION")`.
+/// - `capabilities`: Every optional feature of the derive crate paired with whether it was active.
+///
+/// # Returns
+/// - An inherent impl holding `__{DERIVE}_GENERATOR_VERSION` and `__{DERIVE}_CAPABILITIES`.
+///
+/// # Example
+/// ```rust
+/// let ast: syn::DeriveInput = syn::parse_quote! {
+///     struct User {}
+/// };
+/// let tokens = derive_utils::derive_generator_consts(&ast, "PostgreSQL", "0.0.1", &[("actix", true), ("admin", false)]);
+/// let item: syn::ItemImpl = syn::parse2(tokens).unwrap();
+/// assert_eq!(item.items.len(), 2);
+/// ```
+pub fn derive_generator_consts(
+    ast: &DeriveInput,
+    derive: &str,
+    version: &str,
+    capabilities: &[(&str, bool)]
+) -> proc_macro2::TokenStream {
+    let node = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let prefix = derive.to_uppercase();
+    let version_name = quote::format_ident!("__{}_GENERATOR_VERSION", prefix);
+    let capabilities_name = quote::format_ident!("__{}_CAPABILITIES", prefix);
+    let capabilities = capabilities
+        .iter()
+        .filter(|(_, active)| *active)
+        .map(|(name, _)| *name);
+
+    quote::quote! {
+        impl #impl_generics #node #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub const #version_name: &'static str = #version;
+
+            #[doc(hidden)]
+            pub const #capabilities_name: &'static [&'static str] = &[#(#capabilities),*];
+        }
+    }
+}
+
+/// Appends a generic parameter to a type's generics, lifetimes first.
+///
+/// # Arguments
+/// - `generics`: The generics of the type.
+/// - `param`: The parameter to add, e.g. the `'q` of `sqlx::Encode<'q, _>`.
+///
+/// # Returns
+/// - The impl generics including `param`.
+///
+/// # Example
+/// ```rust
+/// let generics: syn::Generics = syn::parse_quote!(<T: Clone>);
+/// let tokens = derive_utils::derive_merge_generics(&generics, syn::parse_quote!('q));
+/// assert_eq!(tokens.to_string(), "< 'q , T : Clone >");
+/// ```
+pub fn derive_merge_generics(generics: &syn::Generics, param: syn::GenericParam) -> proc_macro2::TokenStream {
+    let mut generics = generics.clone();
+
+    match param {
+        syn::GenericParam::Lifetime(_) => generics.params.insert(0, param),
+        _ => generics.params.push(param),
+    }
+
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    quote::quote!(#impl_generics)
+}
+
+/// Numbers the `{}` of each statement fragment with its postgres placeholder.
+///
+/// # Arguments
+/// - `fragments`: The fragments in bind order, e.g. `email = ${}`.
+/// - `offset`: The number of parameters bound before the first fragment.
+///
+/// # Returns
+/// - The fragments with `{}` replaced by their parameter number.
+///
+/// # Example
+/// ```rust
+/// let updates = derive_utils::derive_number_placeholders(&["email = ${}", "name = ${}"], 0);
+/// assert_eq!(updates, ["email = $1", "name = $2"]);
+///
+/// // Conditions count on after the updated columns
+/// let conditions = derive_utils::derive_number_placeholders(&["id = ${}", "version = ${}"], 2);
+/// assert_eq!(conditions, ["id = $3", "version = $4"]);
+///
+/// let values = derive_utils::derive_number_placeholders(&vec!["${}"; 3], 0);
+/// assert_eq!(values.join(", "), "$1, $2, $3");
+/// ```
+pub fn derive_number_placeholders<T: AsRef<str>>(fragments: &[T], offset: usize) -> Vec<String> {
+    fragments
+        .iter()
+        .enumerate()
+        .map(|(i, fragment)| fragment.as_ref().replace("{}", &(offset + i + 1).to_string()))
+        .collect()
+}
@@ -1,15 +1,41 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, Data, DeriveInput, Ident, Lit, Meta, MetaNameValue, Variant};
 
-#[proc_macro_derive(Enums)]
+#[proc_macro_derive(Enums, attributes(enums))]
 pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
+    let generator = derive_utils::derive_generator_consts(&ast, "Enums", env!("CARGO_PKG_VERSION"), &[]);
     let ident = ast.ident;
     let variants = match ast.data {
         Data::Enum(ref data) => &data.variants,
         _ => panic!("Enums can only be derived for enums"),
     };
 
+    // Flags take their bits from the variants, stored masks must not depend on declaration order
+    let flags = match is_flags(&ast.attrs) {
+        Ok(true) => match variants.iter().map(variant_bit).collect::<syn::Result<Vec<u32>>>() {
+            Ok(bits) => Some(bits),
+            Err(e) => return e.to_compile_error().into(),
+        },
+        Ok(false) => match variants.iter().flat_map(|v| &v.attrs).find(|attr| attr.path().is_ident("enums")) {
+            Some(attr) => return syn::Error::new_spanned(attr, "#[enums(bit = ...)] is only supported on #[enums(flags)] enums")
+                .to_compile_error()
+                .into(),
+            None => None,
+        },
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    if let Some(bits) = &flags {
+        for (i, bit) in bits.iter().enumerate() {
+            if let Some(first) = bits[..i].iter().position(|b| b == bit) {
+                let message = format!("bit {} of `{}` is already used by `{}`", bit, variants[i].ident, variants[first].ident);
+
+                return syn::Error::new_spanned(&variants[i].ident, message).to_compile_error().into();
+            }
+        }
+    }
+
     // Find default variant
     let default_variant = variants.iter().find(|variant| {
         variant.attrs.iter().any(|attr| attr.path().is_ident("default"))
@@ -23,6 +49,29 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
     
     let default_variant_ident = &default_variant.ident;
 
+    // Values are matched case-insensitively, so duplicates would shadow each other
+    let mut seen = Vec::<(String, &Ident)>::with_capacity(variants.len());
+    for variant in variants.iter() {
+        let value = extract_rename_value(variant);
+        let lowered = value.to_lowercase();
+
+        if let Some((_, first)) = seen.iter().find(|(l, _)| *l == lowered) {
+            let message = format!(
+                "variant `{}` serializes to `{}`, which is already used by `{}` (values are compared case-insensitively)",
+                variant.ident, value, first
+            );
+
+            return match variant.attrs.iter().find(|attr| attr.path().is_ident("sqlx")) {
+                Some(attr) => syn::Error::new_spanned(attr, message),
+                None => syn::Error::new_spanned(&variant.ident, message),
+            }
+            .to_compile_error()
+            .into();
+        }
+
+        seen.push((lowered, &variant.ident));
+    }
+
     // Collect variants and their rename values
     let variants: Vec<(Ident, String, String)> = variants
         .iter()
@@ -34,17 +83,36 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
         .collect();
 
     let mut variant_ident = vec![];
+    let mut variant_name = vec![];
     let mut variant_string = vec![];
     let mut variant_lowered = vec![];
 
     for (v, s, l) in variants {
+        variant_name.push(v.to_string());
         variant_ident.push(v);
         variant_string.push(s);
         variant_lowered.push(l);
     }
 
 
-    let token = quote::quote!{
+    let mut token = quote::quote!{
+        impl #ident {
+            pub const VALUES: &'static [&'static str] = &[#(#variant_string,)*];
+
+            pub const VARIANT_NAMES: &'static [&'static str] = &[#(#variant_name,)*];
+
+            pub fn map<V>(f: impl Fn(Self) -> V) -> std::collections::HashMap<Self, V>
+            where
+                // Higher-ranked so enums without Hash/Eq still compile
+                for<'a> Self: std::hash::Hash + Eq,
+            {
+                let mut map = std::collections::HashMap::with_capacity(Self::VALUES.len());
+                #(map.insert(Self::#variant_ident, f(Self::#variant_ident));)*
+
+                map
+            }
+        }
+
         impl std::fmt::Display for #ident {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 let text = match self {
@@ -138,23 +206,231 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
 
     };
 
+    // Generate a bit set of the variants when flags are requested
+    if let Some(bits) = flags {
+        let set = quote::format_ident!("{}Set", ident);
+        let bits = bits.into_iter().map(|bit| 1u64 << bit).collect::<Vec<_>>();
+        let error = format!("Unknown {} flag", ident);
+
+        token.extend(quote::quote! {
+            impl #ident {
+                pub fn bit(&self) -> u64 {
+                    match self {
+                        #(Self::#variant_ident => #bits,)*
+                    }
+                }
+            }
+
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            pub struct #set(u64);
+
+            impl #set {
+                pub const ALL: Self = Self(0 #(| #bits)*);
+
+                pub const fn empty() -> Self {
+                    Self(0)
+                }
+
+                pub const fn bits(&self) -> u64 {
+                    self.0
+                }
+
+                pub const fn from_bits(bits: u64) -> Self {
+                    Self(bits & Self::ALL.0)
+                }
+
+                pub const fn is_empty(&self) -> bool {
+                    self.0 == 0
+                }
+
+                pub fn contains(&self, value: #ident) -> bool {
+                    self.0 & value.bit() != 0
+                }
+
+                pub fn insert(&mut self, value: #ident) {
+                    self.0 |= value.bit();
+                }
+
+                pub fn remove(&mut self, value: #ident) {
+                    self.0 &= !value.bit();
+                }
+
+                pub fn with(mut self, value: #ident) -> Self {
+                    self.insert(value);
+
+                    self
+                }
+
+                pub const fn union(self, other: Self) -> Self {
+                    Self(self.0 | other.0)
+                }
+
+                pub const fn intersection(self, other: Self) -> Self {
+                    Self(self.0 & other.0)
+                }
+
+                pub const fn difference(self, other: Self) -> Self {
+                    Self(self.0 & !other.0)
+                }
+
+                pub fn iter(&self) -> impl Iterator<Item = #ident> + '_ {
+                    [#(#ident::#variant_ident,)*]
+                        .into_iter()
+                        .filter(|v| self.0 & v.bit() != 0)
+                }
+            }
+
+            impl From<#ident> for #set {
+                fn from(value: #ident) -> Self {
+                    Self(value.bit())
+                }
+            }
+
+            impl FromIterator<#ident> for #set {
+                fn from_iter<I: IntoIterator<Item = #ident>>(iter: I) -> Self {
+                    iter.into_iter().fold(Self::empty(), |set, value| set.with(value))
+                }
+            }
+
+            impl std::ops::BitOr for #set {
+                type Output = Self;
+
+                fn bitor(self, other: Self) -> Self {
+                    self.union(other)
+                }
+            }
+
+            impl std::ops::BitAnd for #set {
+                type Output = Self;
+
+                fn bitand(self, other: Self) -> Self {
+                    self.intersection(other)
+                }
+            }
+
+            impl std::fmt::Display for #set {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    let values = self.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+
+                    write!(f, "{}", values.join("|"))
+                }
+            }
+
+            impl std::str::FromStr for #set {
+                type Err = String;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    let mut set = Self::empty();
+
+                    for flag in value.split('|').map(|f| f.trim()).filter(|f| !f.is_empty()) {
+                        match flag.to_lowercase().as_str() {
+                            #(#variant_lowered => set.insert(#ident::#variant_ident),)*
+                            _ => return Err(format!("{} `{}`", #error, flag))
+                        }
+                    }
+
+                    Ok(set)
+                }
+            }
+
+            impl serde::Serialize for #set {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+                    serializer.serialize_str(&self.to_string())
+                }
+            }
+
+            impl<'de> serde::de::Deserialize<'de> for #set {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    let value = String::deserialize(deserializer)?;
+
+                    value.parse().map_err(serde::de::Error::custom)
+                }
+            }
+
+            impl sqlx::Type<sqlx::Postgres> for #set {
+                fn type_info() -> sqlx::postgres::PgTypeInfo {
+                    <i64 as sqlx::Type<sqlx::Postgres>>::type_info()
+                }
+            }
+
+            impl<'q> sqlx::Encode<'q, sqlx::Postgres> for #set {
+                fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+                    <i64 as sqlx::Encode<'q, sqlx::Postgres>>::encode(self.0 as i64, buf)
+                }
+            }
+
+            impl<'r> sqlx::Decode<'r, sqlx::Postgres> for #set {
+                fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                    Ok(Self::from_bits(<i64 as sqlx::Decode<'r, sqlx::Postgres>>::decode(value)? as u64))
+                }
+            }
+        });
+    }
+
+    token.extend(generator);
+
     token.into()
 }
 
+// Check for the `#[enums(flags)]` attribute, rejecting unknown keys
+fn is_flags(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut flags = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("enums")) {
+        attr.parse_nested_meta(|meta| match meta.path.is_ident("flags") {
+            true => {
+                flags = true;
+                Ok(())
+            },
+            false => Err(meta.error("unknown enums attribute, expected one of: flags"))
+        })?;
+    }
+
+    Ok(flags)
+}
+
+// Read the `#[enums(bit = N)]` every variant of a flags enum declares
+fn variant_bit(variant: &Variant) -> syn::Result<u32> {
+    let mut bit = None;
+
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("enums")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("bit") {
+                return Err(meta.error("unknown enums variant attribute, expected one of: bit"));
+            }
+
+            let value = meta.value()?.parse::<syn::LitInt>()?;
+            match value.base10_parse::<u32>()? {
+                value @ 0..=63 => bit = Some(value),
+                _ => return Err(syn::Error::new(value.span(), "bit must be between 0 and 63"))
+            }
+
+            Ok(())
+        })?;
+    }
+
+    bit.ok_or_else(|| syn::Error::new_spanned(
+        &variant.ident,
+        format!("flags variant `{}` needs an explicit #[enums(bit = ...)]", variant.ident)
+    ))
+}
+
 
 
 fn extract_rename_value(variant: &Variant) -> String {
     for attr in &variant.attrs {
-        if attr.path().is_ident("sqlx") {
-            if let Ok(Meta::NameValue(MetaNameValue {
+        if attr.path().is_ident("sqlx")
+            && let Ok(Meta::NameValue(MetaNameValue {
               value: syn::Expr::Lit(syn::ExprLit {
                     lit: Lit::Str(lit_str),
                     ..
                 }),
               ..
               })) = attr.parse_args::<Meta>() {
-                return lit_str.value();
-            }
+            return lit_str.value();
         }
     }
 
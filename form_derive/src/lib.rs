@@ -1,29 +1,36 @@
-use deluxe::ExtractAttributes;
 use proc_macro::TokenStream as TS1;
 use proc_macro2::{Ident, TokenStream as TS2};
 use quote::format_ident;
 use syn::{DeriveInput, LitBool, LitStr, Type};
 
 // Set ReferenceAttrs
-#[derive(Default, Debug, ExtractAttributes)]
-#[deluxe(attributes(reference))]
-struct ReferenceAttrs {
-    pub model: Option<Ident>
+derive_utils::attr_spec! {
+    #[deluxe(attributes(reference))]
+    struct ReferenceAttrs {
+        pub model: Option<Ident>,
+        pub filter: Option<syn::Path>,
+    }
 }
 
 // Set FormAttrs struct
-#[derive(Default, Debug, deluxe::ExtractAttributes)]
-#[deluxe(attributes(form))]
-struct FormAttrs {
-    pub sanitize: Option<LitStr>,
-    pub error: Option<Type>,
-    pub skip_refs: Option<LitBool>
+derive_utils::attr_spec! {
+    #[deluxe(attributes(form))]
+    struct FormAttrs {
+        pub sanitize: Option<LitStr>,
+        pub error: Option<Type>,
+        pub skip_refs: Option<LitBool>,
+        pub filter: Option<LitStr>,
+        pub example: Option<LitStr>,
+        pub error_message: Option<LitStr>,
+    }
 }
 
 // Start of derive and field attribute derives
 #[proc_macro_derive(Form, attributes(form, reference))]
 pub fn main(stream: proc_macro::TokenStream) -> TS1 {
-    derive(stream.into()).unwrap().into()
+    derive(stream.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
 // Start of derive and token processing
@@ -36,6 +43,10 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
     let mut token = quote::quote!{};
     let reference_attrs = derive_utils::derive_struct_attrs::<ReferenceAttrs>(&ast);
 
+    // Validate attributes against their registered grammar
+    derive_utils::derive_validate_attrs::<ReferenceAttrs>(&ast.attrs)?;
+    derive_utils::derive_validate_fields::<FormAttrs>(&ast)?;
+
     // Create error & response node
     let node_error = format_ident!("{}Error", node.to_string().replace("Form", ""));
 
@@ -47,6 +58,9 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
     let mut error_types = vec![];
     let mut cloned_fields = vec![];
     let mut all_props = vec![];
+    let mut filters = vec![];
+    let mut examples = vec![];
+    let mut rejections = vec![];
 
     // Loop through all fields
     for (
@@ -57,14 +71,19 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
     ) in
         derive_utils::derive_all_fields::<&str, FormAttrs>(&ast, "form")
     {
-        // Set type string
-        let ty_to_str = derive_utils::derive_type_to_string(&ty);
-        let inner_ty = derive_utils::derive_parse_inner_type(&ty);
-        if ty_to_str.starts_with("Null") {
+        // Classify the field type
+        let kind = derive_utils::derive_field_kind(&ty);
+        let inner_ty = kind.inner().clone();
+        if kind.is_null() {
+            let ref_name = format_ident!("{}_ref", field);
             all_props.push(quote::quote! {
                 pub fn #field(&self) -> Option<#inner_ty> {
                     self.#field.clone().take()
                 }
+
+                pub fn #ref_name(&self) -> Option<&#inner_ty> {
+                    self.#field.as_ref().take()
+                }
             });
         }
 
@@ -128,6 +147,43 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
             }
         }
 
+        // Set examples, strings are kept verbatim and other types are read as JSON
+        if let Some(example) = attrs.example.clone() {
+            let value = match derive_utils::derive_normalize_type(&inner_ty).as_str() {
+                "String" => quote::quote! { serde_json::Value::String(#example.to_string()) },
+                _ => quote::quote! {
+                    serde_json::from_str::<serde_json::Value>(#example)
+                        .unwrap_or_else(|_| serde_json::Value::String(#example.to_string()))
+                }
+            };
+
+            examples.push(quote::quote! {
+                data.#field = serde_json::from_value(#value).unwrap_or_default();
+            });
+        }
+
+        // Set query builder filters
+        if let Some(attr) = attrs.filter.clone() {
+            let method = format_ident!("{}_{}", field, attr.value());
+
+            match attr.value().as_str() {
+                "eq" | "ne" | "lt" | "gt" | "contains" | "any" => filters.push(quote::quote! {
+                    if let Some(value) = data.#field.clone().take() {
+                        builder = builder.#method(value);
+                    }
+                }),
+                "in" | "array_contains" | "overlaps" => filters.push(quote::quote! {
+                    if let Some(value) = data.#field.clone().take() {
+                        builder = builder.#method(&value);
+                    }
+                }),
+                _ => return Err(syn::Error::new(
+                    attr.span(),
+                    format!("unknown filter `{}`", attr.value())
+                ))
+            }
+        }
+
         // Set errors
         error_fields.push(field.clone());
         error_types.push(match () {
@@ -139,6 +195,23 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
             #[serde(skip_serializing_if = "Null::undefined")]
         });
 
+        // Set error messages, read through serde so any error type holding a string works
+        if let Some(message) = attrs.error_message.clone() {
+            let reject = format_ident!("reject_{}", field);
+            rejections.push(quote::quote! {
+                /// Marks the field as invalid using the message declared next to it.
+                ///
+                /// # Returns
+                /// A copy of the error with the field populated.
+                pub fn #reject(mut self) -> Self {
+                    self.#field = serde_json::from_value(serde_json::Value::String(#message.to_string()))
+                        .unwrap_or_default();
+
+                    self
+                }
+            });
+        }
+
         let cloned_field = format_ident!("clone_{}", field);
         cloned_fields.push(quote::quote!{
             pub fn #cloned_field(&self, value: &#ty) -> Self {
@@ -162,14 +235,6 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
                 *self == Self::default()
             }
 
-            /// Converts the current instance to another type `T` that implements `From<Self>`.
-            ///
-            /// # Returns
-            /// An instance of type `T`.
-            pub fn to<T: From<Self>>(&self) -> T {
-                T::from(self.clone())
-            }
-
             /// Converts the current instance to the associated error type `Self::Error`.
             ///
             /// # Returns
@@ -198,6 +263,18 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
                 data
             }
 
+            /// Builds an example payload from the `example` attributes of each field.
+            ///
+            /// # Returns
+            /// A JSON value suitable for API documentation, set on the utoipa schema when that feature is enabled.
+            pub fn example_json() -> serde_json::Value {
+                let mut data = Self::default();
+
+                #(#examples)*
+
+                serde_json::to_value(data).unwrap_or(serde_json::Value::Null)
+            }
+
             #(#all_props)*
 
             #(#cloned_fields)*
@@ -226,16 +303,43 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
 
                 Err(responder::to(self))
             }
+
+            #(#rejections)*
         }
+    });
 
-        impl actix_web::Responder for #node_error {
-            type Body = actix_web::body::BoxBody;
+    // Create Responder implementation
+    if cfg!(feature = "actix") {
+        token.extend(quote::quote! {
+            impl actix_web::Responder for #node_error {
+                type Body = actix_web::body::BoxBody;
 
-            fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
-                actix_web::HttpResponse::Ok().json(self)
+                fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+                    actix_web::HttpResponse::Ok().json(self)
+                }
             }
-        }
-    });
+        });
+    }
+
+    // Check if a query builder filter exists
+    if let Some(builder) = reference_attrs.filter {
+        token.extend(quote::quote! {
+            impl #node {
+                /// Converts the sanitized instance into a query builder filtering on every set field.
+                ///
+                /// # Returns
+                /// A query builder with one condition per annotated field holding a value.
+                pub fn to_filter(&self) -> #builder {
+                    let data = self.sanitize();
+                    let mut builder = <#builder>::new();
+
+                    #(#filters)*
+
+                    builder
+                }
+            }
+        });
+    }
 
     // Check if reference exists
     if let Some(refs) = reference_attrs.model {
@@ -266,6 +370,42 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
         });
     }
 
+    // Create utoipa modifier setting the example of the form's schema
+    if cfg!(feature = "utoipa") {
+        let node_example = format_ident!("{}Example", node);
+        let schema = node.to_string();
+
+        token.extend(quote::quote! {
+            /// Sets `example_json()` as the example of the form's utoipa schema.
+            ///
+            /// Register it with `#[openapi(modifiers(&...))]` next to the schema in `components(schemas(...))`.
+            pub struct #node_example;
+
+            impl utoipa::Modify for #node_example {
+                fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+                    let schema = openapi.components
+                        .as_mut()
+                        .and_then(|components| components.schemas.get_mut(#schema));
+
+                    if let Some(utoipa::openapi::RefOr::T(utoipa::openapi::schema::Schema::Object(object))) = schema {
+                        object.examples = vec![#node::example_json()];
+                    }
+                }
+            }
+        });
+    }
+
+    // Create To implementation
+    token.extend(quote::quote!{
+        impl derives::To for #node {}
+    });
+
+    // Create generator constants
+    token.extend(derive_utils::derive_generator_consts(&ast, "Form", env!("CARGO_PKG_VERSION"), &[
+        ("actix", cfg!(feature = "actix")),
+        ("utoipa", cfg!(feature = "utoipa")),
+    ]));
+
     // Return the new token
     Ok(token)
 }
\ No newline at end of file
@@ -1,21 +1,21 @@
-use proc_macro::TokenStream;
-use syn::DeriveInput;
+use proc_macro::TokenStream as TS1;
+use derive_utils::Compose;
+
+// Expanded standalone, owning every block
+const IS_EMPTY: Compose = Compose {
+    version: env!("CARGO_PKG_VERSION"),
+    actix: false,
+    audit: false,
+    skip: &[],
+    responder: None,
+    nulls: None,
+};
 
 // Entry point for our macro
 #[proc_macro_derive(IsEmpty)]
-pub fn main(stream: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(stream).unwrap();
-    let node = ast.ident;
-
-    TokenStream::from(quote::quote! {
-        impl #node {
-            /// Checks if the current instance is equivalent to the default value of its type.
-            ///
-            /// # Returns
-            /// - `bool` - `true` if `self` is equal to the default value, otherwise `false`.
-            pub fn is_empty(&self) -> bool {
-                *self == Self::default()
-            }
-        }
-    })
-}
\ No newline at end of file
+pub fn main(stream: TS1) -> TS1 {
+    syn::parse::<syn::DeriveInput>(stream)
+        .and_then(|ast| derive_utils::is_empty::derive_is_empty(&ast, &IS_EMPTY))
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,23 @@
+use proc_macro::TokenStream as TS1;
+use derive_utils::dialect::Dialect;
+
+// The MySQL dialect, written records are read back by id as MySQL has no RETURNING
+const MYSQL: Dialect = Dialect {
+    name: "MySQL",
+    version: env!("CARGO_PKG_VERSION"),
+    quote: '`',
+    placeholder: "?",
+    now: "NOW()",
+    row: "sqlx::mysql::MySqlRow",
+    pool: "sqlx::MySqlPool",
+    returning: false,
+    actix: cfg!(feature = "actix"),
+};
+
+// Start of derive and field attribute derives
+#[proc_macro_derive(MySQL, attributes(table, column))]
+pub fn main(stream: TS1) -> TS1 {
+    derive_utils::dialect::derive_sql_model(stream.into(), &MYSQL)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
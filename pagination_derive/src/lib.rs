@@ -0,0 +1,184 @@
+use proc_macro::TokenStream as TS1;
+use proc_macro2::TokenStream as TS2;
+use syn::{Data, DeriveInput, LitInt, LitStr};
+
+// Pagination attribute
+derive_utils::attr_spec! {
+    #[deluxe(attributes(pagination))]
+    struct PaginationAttrs {
+        per_page: Option<LitInt>,
+        max_per_page: Option<LitInt>,
+        sort: Option<LitStr>,
+        sorts: Option<LitStr>,
+        order: Option<LitStr>,
+    }
+}
+
+// Start of derive and field attribute derives
+#[proc_macro_derive(Pagination, attributes(pagination))]
+pub fn main(stream: TS1) -> TS1 {
+    derive(stream.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// Start of derive and token processing
+fn derive(stream: TS2) -> deluxe::Result<TS2> {
+    // Parse token stream
+    let ast: DeriveInput = syn::parse2(stream)?;
+    let node = &ast.ident.clone();
+
+    // Create main token stream
+    let mut token = quote::quote!{};
+    let attrs = derive_utils::derive_struct_attrs::<PaginationAttrs>(&ast);
+
+    // Validate attributes against their registered grammar
+    derive_utils::derive_validate_attrs::<PaginationAttrs>(&ast.attrs)?;
+
+    // Make sure the paging fields exist with the types the generated code assigns
+    let fields = match &ast.data {
+        Data::Struct(data) => data.fields
+            .iter()
+            .filter(|f| f.ident.is_some())
+            .collect::<Vec<&syn::Field>>(),
+        _ => return Err(syn::Error::new(node.span(), "Pagination can only be derived for structs")),
+    };
+
+    for name in ["page", "per_page", "sort", "order"] {
+        let field = match fields.iter().find(|f| f.ident.as_ref().is_some_and(|i| i == name)) {
+            Some(field) => field,
+            None => return Err(syn::Error::new(node.span(), format!("Pagination requires a `{}` field", name))),
+        };
+
+        if ["page", "per_page"].contains(&name) && !derive_utils::derive_type_matches(&field.ty, "i64") {
+            return Err(syn::Error::new_spanned(&field.ty, format!("Pagination requires `{}` to be an `i64`", name)));
+        }
+    }
+
+    // Create paging defaults
+    let per_page = match &attrs.per_page {
+        Some(lit) => lit.base10_parse::<i64>()?,
+        None => 20,
+    };
+
+    let max_per_page = match &attrs.max_per_page {
+        Some(lit) => lit.base10_parse::<i64>()?,
+        None => 100,
+    };
+
+    if per_page < 1 || max_per_page < per_page {
+        return Err(syn::Error::new(node.span(), "Pagination requires 1 <= per_page <= max_per_page"));
+    }
+
+    let sorts = attrs.sorts
+        .as_ref()
+        .map(|s| s.value()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<String>>())
+        .unwrap_or_default();
+
+    let sort = attrs.sort
+        .as_ref()
+        .map(|s| s.value())
+        .or(sorts.first().cloned())
+        .unwrap_or("id".to_string());
+
+    if !sorts.is_empty() && !sorts.contains(&sort) {
+        let span = attrs.sort.as_ref().map(|s| s.span()).unwrap_or(node.span());
+        return Err(syn::Error::new(span, format!("Default sort `{}` is not one of the allowed sorts", sort)));
+    }
+
+    let order = match &attrs.order {
+        Some(lit) if ["asc", "desc"].contains(&lit.value().to_lowercase().as_str()) => lit.value().to_lowercase(),
+        Some(lit) => return Err(syn::Error::new(lit.span(), "Pagination order must be `asc` or `desc`")),
+        None => "asc".to_string(),
+    };
+
+    // Create Pagination implementation
+    //____________________________________________________________
+    token.extend(quote::quote!{
+        impl #node {
+            pub const DEFAULT_PER_PAGE: i64 = #per_page;
+            pub const MAX_PER_PAGE: i64 = #max_per_page;
+            pub const SORTS: &'static [&'static str] = &[#(#sorts,)*];
+
+            /// Builds paging inputs from query parameter pairs.
+            ///
+            /// Reads `page`, `perPage` (or `per_page`), `sort` and `order`, falling back to the
+            /// configured defaults for missing or invalid values and clamping `page` and `perPage`.
+            ///
+            /// # Returns
+            /// - `Self` - the normalized paging inputs.
+            #[allow(clippy::needless_update)]
+            pub fn from_pairs<I, K, V>(pairs: I) -> Self
+            where
+                I: IntoIterator<Item = (K, V)>,
+                K: AsRef<str>,
+                V: AsRef<str>
+            {
+                let mut page = 1;
+                let mut per_page = Self::DEFAULT_PER_PAGE;
+                let mut sort = #sort.to_string();
+                let mut order = #order;
+
+                for (key, value) in pairs {
+                    let value = value.as_ref().trim();
+
+                    match key.as_ref() {
+                        "page" => page = value.parse().unwrap_or(1),
+                        "perPage" | "per_page" => per_page = value.parse().unwrap_or(Self::DEFAULT_PER_PAGE),
+                        "sort" if !value.is_empty() && (Self::SORTS.is_empty() || Self::SORTS.contains(&value)) => {
+                            sort = value.to_string();
+                        },
+                        "order" if value.eq_ignore_ascii_case("asc") => order = "asc",
+                        "order" if value.eq_ignore_ascii_case("desc") => order = "desc",
+                        _ => {}
+                    }
+                }
+
+                Self {
+                    page: page.max(1),
+                    per_page: per_page.clamp(1, Self::MAX_PER_PAGE),
+                    sort,
+                    order: order.to_string(),
+                    ..Default::default()
+                }
+            }
+
+            /// Returns the number of records to skip for the current page.
+            pub fn offset(&self) -> i64 {
+                (self.page - 1).saturating_mul(self.per_page)
+            }
+
+            /// Returns the maximum number of records for the current page.
+            pub fn limit(&self) -> i64 {
+                self.per_page
+            }
+        }
+    });
+
+    // Extractor Related
+    if cfg!(feature = "actix") {
+        token.extend(quote::quote!{
+            impl actix_web::FromRequest for #node {
+                type Error = actix_web::Error;
+                type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+                fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+                    let pairs = actix_web::web::Query::<Vec<(String, String)>>::from_query(req.query_string())
+                        .map(|query| query.into_inner())
+                        .unwrap_or_default();
+
+                    std::future::ready(Ok(Self::from_pairs(pairs)))
+                }
+            }
+        });
+    }
+
+    // Create generator constants
+    token.extend(derive_utils::derive_generator_consts(&ast, "Pagination", env!("CARGO_PKG_VERSION"), &[("actix", cfg!(feature = "actix"))]));
+
+    Ok(token)
+}
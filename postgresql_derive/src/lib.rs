@@ -1,510 +1,3781 @@
-use deluxe::ExtractAttributes;
 use proc_macro::TokenStream as TS1;
 use proc_macro2::{Ident, TokenStream as TS2};
+use derive_utils::FieldKind;
 use quote::format_ident;
-use std::collections::HashMap;
-use syn::{DeriveInput, LitStr, Type};
+use syn::{parse_quote, punctuated::Punctuated, Attribute, DeriveInput, LitStr, Meta, Token, Type};
 
 // Table attribute
-#[derive(Default, Debug, ExtractAttributes)]
-#[deluxe(attributes(table))]
-struct TableAttrs {
-    alias: Option<LitStr>,
-    rename: Option<LitStr>,
+derive_utils::attr_spec! {
+    #[deluxe(attributes(table))]
+    struct TableAttrs {
+        alias: Option<LitStr>,
+        rename: Option<LitStr>,
+        generate: Option<LitStr>,
+        skip_generate: Option<LitStr>,
+        cache: Option<LitStr>,
+        id: Option<LitStr>,
+        driver: Option<LitStr>,
+        responder: Option<LitStr>,
+        nulls: Option<LitStr>,
+        notify: deluxe::Flag,
+        healthcheck: deluxe::Flag,
+        sqlcommenter: deluxe::Flag,
+        tenant: Option<LitStr>,
+        retention: Nested<RetentionAttrs>,
+        projection: Nested<ProjectionAttrs>,
+        metrics: Option<LitStr>,
+        timeout_ms: Option<syn::LitInt>,
+        retries: Option<syn::LitInt>,
+        returning: Option<LitStr>,
+        base: deluxe::Flag,
+        extends: Option<LitStr>,
+    }
+}
+
+// Retention attribute nested in the table attribute
+#[derive(Debug, deluxe::ParseMetaItem)]
+struct RetentionAttrs {
+    column: LitStr,
+    days: syn::LitInt,
+    #[deluxe(default)]
+    soft_delete: Option<LitStr>,
+}
+
+// Projection attribute nested in the table attribute
+#[derive(Debug, deluxe::ParseMetaItem)]
+struct ProjectionAttrs {
+    name: LitStr,
+    fields: LitStr,
+}
+
+// Optional nested attribute, Option<T> would only accept the braced `key = { ... }` form
+#[derive(Debug)]
+struct Nested<T>(Option<T>);
+
+impl<T> Default for Nested<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: deluxe::ParseMetaItem> deluxe::ParseMetaItem for Nested<T> {
+    fn parse_meta_item(input: syn::parse::ParseStream, mode: deluxe::ParseMode) -> deluxe::Result<Self> {
+        T::parse_meta_item(input, mode).map(|attrs| Self(Some(attrs)))
+    }
+
+    fn parse_meta_item_named(input: syn::parse::ParseStream, name: &str, span: proc_macro2::Span) -> deluxe::Result<Self> {
+        T::parse_meta_item_named(input, name, span).map(|attrs| Self(Some(attrs)))
+    }
+
+    fn missing_meta_item(_name: &str, _span: proc_macro2::Span) -> deluxe::Result<Self> {
+        Ok(Self(None))
+    }
 }
 
 // Column attribute
-#[derive(Default, Debug, ExtractAttributes)]
-#[deluxe(attributes(column))]  // Fixed typo: columnn -> column
-struct ColumnAttrs {}
+derive_utils::attr_spec! {
+    #[deluxe(attributes(column))]  // Fixed typo: columnn -> column
+    struct ColumnAttrs {
+        skip_compare: deluxe::Flag,
+        version: deluxe::Flag,
+        created_at: deluxe::Flag,
+        updated_at: deluxe::Flag,
+        skip_update: deluxe::Flag,
+        readonly: deluxe::Flag,
+        tsvector: deluxe::Flag,
+        language: Option<LitStr>,
+        rename: Option<LitStr>,
+        #[deluxe(rename = enum)]
+        is_enum: deluxe::Flag,
+        jsonb: deluxe::Flag,
+        getters: Option<LitStr>,
+        no_setter: deluxe::Flag,
+        computed: Option<LitStr>,
+        ignore: deluxe::Flag,
+        private: deluxe::Flag,
+        parse_with: Option<LitStr>,
+        unique: Option<LitStr>,
+        check: Option<LitStr>,
+    }
+}
+
+// Relation attribute
+derive_utils::attr_spec! {
+    #[deluxe(attributes(relation))]
+    struct RelationAttrs {
+        belongs_to: Option<LitStr>,
+        has_many: Option<LitStr>,
+        fk: Option<LitStr>,
+    }
+}
 
 // Start of derive and field attribute derives
-#[proc_macro_derive(PostgreSQL, attributes(table, column))]
+#[proc_macro_derive(PostgreSQL, attributes(table, column, relation))]
 pub fn main(stream: TS1) -> TS1 {
-    derive(stream.into()).unwrap().into()
+    derive(stream.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// Start of umbrella derive sharing a single model attribute namespace
+#[proc_macro_derive(Model, attributes(model, column, relation, encryption))]
+pub fn model(stream: TS1) -> TS1 {
+    derive_model(stream.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// The generators composed into a model leave the blocks PostgreSQL owns to it
+const COMPOSED_ENCRYPTION: derive_utils::Compose = derive_utils::Compose {
+    version: env!("CARGO_PKG_VERSION"),
+    actix: cfg!(feature = "actix"),
+    audit: cfg!(feature = "audit"),
+    skip: &["getters", "is_empty", "parsers", "responder", "sqlx", "to"],
+    responder: None,
+    nulls: None,
+};
+
+const COMPOSED: derive_utils::Compose = derive_utils::Compose {
+    version: env!("CARGO_PKG_VERSION"),
+    actix: cfg!(feature = "actix"),
+    audit: cfg!(feature = "audit"),
+    skip: &["is_empty", "parsers"],
+    responder: None,
+    nulls: None,
+};
+
+// Split model attributes into the namespaces of the composed generators and compose them
+fn derive_model(stream: TS2) -> deluxe::Result<TS2> {
+    use derive_utils::AttrSpec;
+    use derive_utils::encryption::EncryptionStructAttrs;
+    use derive_utils::jsonb::JsonbAttrs;
+
+    let mut ast: DeriveInput = syn::parse2(stream)?;
+    let node = &ast.ident.clone();
+
+    let (mut is_jsonb, mut is_encryption, mut is_empty) = (false, false, false);
+    let (mut table, mut jsonb, mut encryption) = (vec![], vec![], vec![]);
+    let mut attrs = Vec::<Attribute>::with_capacity(ast.attrs.len());
+
+    let is_key = |meta: &Meta, keys: &[(&str, &str)]| keys.iter().any(|(key, _)| meta.path().is_ident(key));
+
+    for attr in ast.attrs.drain(..) {
+        if !attr.path().is_ident("model") {
+            attrs.push(attr);
+            continue;
+        }
+
+        for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+            match &meta {
+                meta if meta.path().is_ident("jsonb") => is_jsonb = true,
+                meta if meta.path().is_ident("encryption") => is_encryption = true,
+                meta if meta.path().is_ident("is_empty") => is_empty = true,
+                meta if is_key(meta, JsonbAttrs::SUPPORTED_ATTRIBUTES) => jsonb.push(meta.clone()),
+                meta if is_key(meta, EncryptionStructAttrs::SUPPORTED_ATTRIBUTES) => encryption.push(meta.clone()),
+                meta => table.push(meta.clone())
+            }
+        }
+    }
+
+    if !table.is_empty() {
+        attrs.push(parse_quote!(#[table(#(#table),*)]));
+    }
+
+    if !jsonb.is_empty() && !is_jsonb {
+        return Err(syn::Error::new(node.span(), format!("jsonb settings on {} require #[model(jsonb)]", node)));
+    }
+
+    if !encryption.is_empty() && !is_encryption {
+        return Err(syn::Error::new(node.span(), format!("encryption settings on {} require #[model(encryption)]", node)));
+    }
+
+    if is_encryption && !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(node.span(), format!("encryption is not supported on generic model {}", node)));
+    }
+
+    // Each generator only sees its own namespace
+    let mut composed = ast.clone();
+    ast.attrs = attrs.clone();
+
+    if !jsonb.is_empty() {
+        attrs.push(parse_quote!(#[jsonb(#(#jsonb),*)]));
+    }
+
+    if !encryption.is_empty() {
+        attrs.push(parse_quote!(#[encryption(#(#encryption),*)]));
+    }
+
+    composed.attrs = attrs;
+
+    // Composed generators name the crates configured on the table
+    let path = |key: &str| match table.iter().find(|meta| meta.path().is_ident(key)) {
+        Some(Meta::NameValue(syn::MetaNameValue { value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(path), .. }), .. })) => {
+            path.parse::<syn::Path>().map(Some)
+        },
+        _ => Ok(None)
+    };
+    let (responder, nulls) = (path("responder")?, path("nulls")?);
+
+    // The input is parsed once and handed to every generator
+    let mut token = derive_table(&ast)?;
+
+    if is_encryption {
+        let compose = derive_utils::Compose { responder: responder.clone(), nulls: nulls.clone(), ..COMPOSED_ENCRYPTION };
+        token.extend(derive_utils::encryption::derive_encryption(&composed, &compose)?);
+    }
+
+    let compose = derive_utils::Compose { responder, nulls, ..COMPOSED };
+
+    if is_empty {
+        token.extend(derive_utils::is_empty::derive_is_empty(&composed, &compose)?);
+    }
+
+    if is_jsonb {
+        token.extend(derive_utils::jsonb::derive_jsonb(&composed, &compose)?);
+    }
+
+    Ok(token)
+}
+
+// Create fields macro of a base struct, pasting its columns into extending models
+fn derive_base(ast: &DeriveInput) -> deluxe::Result<TS2> {
+    let node = &ast.ident;
+
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            node.span(),
+            format!("base is not supported on generic struct {}", node)
+        ));
+    }
+
+    let syn::Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new(node.span(), format!("base {} must be a struct", node)));
+    };
+
+    let fields = data.fields.iter().filter_map(|f| f.ident.as_ref()).collect::<Vec<_>>();
+    let definitions = data.fields.iter().map(|f| {
+        let (attrs, vis, ident, ty) = (&f.attrs, &f.vis, &f.ident, &f.ty);
+        quote::quote!(#(#attrs)* #vis #ident: #ty,)
+    });
+
+    let base = derive_base_macro(&syn::Path::from(node.clone()));
+    let doc = format!("Prepends the columns of {} to the struct, extend it with `#[table(extends = \"{}\")]`", node, node);
+
+    Ok(quote::quote!{
+        #[doc = #doc]
+        macro_rules! #base {
+            (@from $base:path, $child:ident) => {
+                impl From<&$child> for $base {
+                    fn from(child: &$child) -> Self {
+                        Self {
+                            #(
+                                #fields: child.#fields.clone(),
+                            )*
+                        }
+                    }
+                }
+            };
+            ($(#[$meta:meta])* $vis:vis struct $name:ident { $($body:tt)* }) => {
+                $(#[$meta])*
+                $vis struct $name {
+                    #(#definitions)*
+                    $($body)*
+                }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #base;
+    })
+}
+
+// Create path of a base struct's fields macro, named after the struct in snake case
+fn derive_base_macro(path: &syn::Path) -> syn::Path {
+    let mut path = path.clone();
+
+    if let Some(segment) = path.segments.last_mut() {
+        segment.ident = format_ident!("{}", derive_utils::derive_snake_case(segment.ident.to_string()));
+    }
+
+    path
 }
 
 // Start of derive and token processing
 fn derive(stream: TS2) -> deluxe::Result<TS2> {
     // Parse token stream
     let ast: DeriveInput = syn::parse2(stream)?;
-    let node = &ast.ident.clone();
+
+    derive_table(&ast)
+}
+
+// Generate the model from its parsed input, shared with the derives composed by `Model`
+fn derive_table(ast: &DeriveInput) -> deluxe::Result<TS2> {
+    // Columns are named after fields, so only named structs are supported
+    derive_utils::derive_named_fields(ast, "PostgreSQL")?;
+
+    // Validate attributes against their registered grammar
+    derive_utils::derive_validate_attrs::<TableAttrs>(&ast.attrs)?;
+    derive_utils::derive_validate_fields::<ColumnAttrs>(ast)?;
+    derive_utils::derive_validate_fields::<RelationAttrs>(ast)?;
+
+    // Malformed attribute values are reported rather than replaced with defaults
+    let table_attrs = derive_utils::derive_try_struct_attrs::<TableAttrs>(ast)?;
+
+    // Base structs only carry shared columns into the models extending them
+    if table_attrs.base.is_set() {
+        return derive_base(ast);
+    }
+
+    // Resolve the table and its columns once, every block reads what it needs from them
+    let table = Table::new(ast, table_attrs)?;
+    let Table { node, impl_generics, ty_generics, where_clause, .. } = &table;
 
     // Create main token stream
     let mut token = quote::quote!{};
-    let table_attrs = derive_utils::derive_struct_attrs::<TableAttrs>(&ast);
-
-    // Create table name
-    let table_name = derive_utils::derive_snake_case(table_attrs.rename
-        .map(|s| s.value())
-        .unwrap_or(node.to_string()));
-
-    let aliases = if let Some(alias) = table_attrs.alias {
-        alias.value()
-            .replace(" ", "")
-            .replace(",", ";")
-            .to_lowercase()
-            .split(';')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>()
-    } else {
-        Vec::<String>::new()  // Specify type explicitly
-    };
-
-    // Create error message
-    let error = format!("No matching record(s) found in {} table", table_name);
-
-    // All column attributed information
-    let mut all_props = Vec::<TS2>::new();  // Specify types explicitly
-    let mut all_setters = Vec::<TS2>::new();
-    let mut all_setter_opts = Vec::<TS2>::new();
-    let mut all_clones = Vec::<TS2>::new();
-    let mut all_clears = Vec::<TS2>::new();
-    let mut all_cleable_fields = Vec::<Ident>::new();
-    let mut all_update_fields = Vec::<Ident>::new();
-    let mut all_update_columns = Vec::<String>::new();
-
-    let mut all_attributed_fields = Vec::<Ident>::new();
-    let mut all_attributed_inner_ty = Vec::<Type>::new();
-    let mut all_attributed_renamed = Vec::<String>::new();
-
-    // Set text values
-    let mut all_const_names = Vec::<Ident>::new();
-    let mut all_aliased = Vec::<String>::new();
-    let mut all_renamed = Vec::<String>::new();
-    let mut all_plain = Vec::<String>::new();
-    let mut all_tabled = Vec::<String>::new();
-
-    let mut map_sub_parser:HashMap<Ident, Vec<(Ident, Type, String)>> = HashMap::new();
-    let mut map_sub_alias:HashMap<Ident, Vec<String>> = HashMap::new();
-
-    // Loop through all fields
-    for (
-        field,
-        ty,
-        is_attributed,
-        _attrs
-    ) in
-        derive_utils::derive_all_fields::<&str, ColumnAttrs>(&ast, "column")
-    {
-        let ty_to_str = derive_utils::derive_type_to_string(&ty);
-        let inner_ty = derive_utils::derive_parse_inner_type(&ty);
-        // let inner_ty_to_str = derive_utils::derive_type_to_string(&inner_ty);
 
-        // Set all update fields
-        if field.to_string().as_str() != "id" && is_attributed {
-            all_update_fields.push(field.clone());
-            all_update_columns.push(format!("{} = ${{}}", field.clone()));
-        }
+    if table.generates("constants") {
+        token.extend(derive_constants(&table));
+    }
 
-        // Create props
-        match ty_to_str.to_lowercase().starts_with("null<") {
-            true => all_props.push(quote::quote! {
-                pub fn #field(&self) -> Option<#inner_ty> {
-                    self.#field.clone().take()
-                }
-            }),
-            false => all_props.push(quote::quote! {
-                pub fn #field(&self) -> #ty {
-                    self.#field.clone()
-                }
-            })
-        }
+    if table.generates("order") && table.attributed().next().is_some() {
+        token.extend(derive_order(&table));
+    }
 
-        // Create setter_opts
-        let setter_opt_name = format_ident!("set_opts_{}", field.clone());
-        all_setter_opts.push(quote::quote! {
-            pub fn #setter_opt_name(mut self, value: &Option<#inner_ty>) -> Self {
-                if let Some(value) = value.clone() {
-                    self.#field = nulls::new(value);
-                }
+    if table.generates("query") {
+        token.extend(derive_query(&table));
+    }
 
-                self
-            }
-        });
+    token.extend(derive_db_error(&table));
 
-        // Create setters
-        let setter_name = format_ident!("set_{}", field.clone());
-        let inner_ty_str = derive_utils::derive_type_to_string(&inner_ty);
+    if table.generates("parsers") {
+        token.extend(derive_parsers(&table));
+    }
 
-        match inner_ty_str.as_str() {
-            "String" => {
-                all_setters.push(quote::quote! {
-                    pub fn #setter_name<T: ToString>(mut self, value: T) -> Self {
-                        self.#field = nulls::new(value.to_string());
+    token.extend(derive_node(&table)?);
+    token.extend(derive_alias_wrappers(&table));
 
-                        self
-                    }
-                });
+    if let Some(cache) = &table.attrs.cache {
+        token.extend(derive_cache(&table, cache)?);
+    }
+
+    if table.attrs.notify.is_set() {
+        token.extend(derive_notify(&table)?);
+    }
+
+    if let Nested(Some(retention)) = &table.attrs.retention {
+        token.extend(derive_retention(&table, retention)?);
+    }
+
+    if table.id().is_some() {
+        token.extend(derive_lock(&table));
+    }
+
+    if table.attrs.healthcheck.is_set() {
+        token.extend(derive_healthcheck(&table));
+    }
+
+    if let Some(extends) = &table.attrs.extends {
+        token.extend(derive_extends(&table, extends)?);
+    }
+
+    if let Nested(Some(projection)) = &table.attrs.projection {
+        token.extend(derive_projection(&table, projection)?);
+    }
+
+    if table.generates("stats") {
+        token.extend(derive_stats(&table));
+    }
+
+    if cfg!(feature = "sqlcommenter") {
+        token.extend(derive_sql_comment(&table));
+    }
+
+    if table.execution.instrumented {
+        token.extend(derive_trace_query(&table));
+    }
+
+    if table.execution.guarded {
+        token.extend(derive_guard_query(&table));
+    }
+
+    if table.generates("insert") {
+        token.extend(derive_insert(&table));
+    }
+
+    if table.generates("update") {
+        token.extend(derive_update(&table));
+    }
+
+    // Admin routes are built over the blocks they call into
+    let has_admin_blocks = ["order", "parsers", "query", "insert", "update", "responder"]
+        .iter()
+        .all(|b| table.generates(b));
+
+    if let (true, true, Some(id)) = (cfg!(feature = "admin"), has_admin_blocks, table.id()) {
+        token.extend(derive_admin(&table, id));
+    }
+
+    if let (true, Some(_)) = (table.is_any, table.id()) {
+        token.extend(derive_any(&table));
+    }
+
+    if table.generates("from_row") {
+        token.extend(derive_from_row(&table));
+    }
+
+    if cfg!(feature = "actix") && table.generates("responder") {
+        token.extend(derive_responder(&table));
+    }
+
+    // Create To implementation
+    token.extend(quote::quote!{
+        impl #impl_generics derives::To for #node #ty_generics #where_clause {}
+    });
+
+    // Create generator constants
+    token.extend(derive_utils::derive_generator_consts(ast, "PostgreSQL", env!("CARGO_PKG_VERSION"), &[
+        ("actix", cfg!(feature = "actix")),
+        ("admin", cfg!(feature = "admin")),
+        ("sqlcommenter", cfg!(feature = "sqlcommenter")),
+        ("tracing", cfg!(feature = "tracing")),
+    ]));
+
+    // Return the new token
+    Ok(token)
+}
+
+// The model and the settings of its table attribute, resolved once and read by every block
+struct Table<'a> {
+    ast: &'a DeriveInput,
+    node: &'a Ident,
+    attrs: TableAttrs,
+    table_name: String,
+    aliases: Vec<String>,
+    generates: Vec<&'static str>,
+    is_any: bool,
+    commenter: TS2,
+    responder: syn::Path,
+    nulls: syn::Path,
+    metrics: Option<syn::Path>,
+    timeout_ms: Option<u64>,
+    retries: u32,
+    execution: Execution,
+    error: String,
+    unchanged: String,
+    db_error: Ident,
+    is_tracked: bool,
+    columns: Vec<Column>,
+    tenant: Option<usize>,
+    returning: String,
+    returned: TS2,
+    impl_generics: syn::ImplGenerics<'a>,
+    ty_generics: syn::TypeGenerics<'a>,
+    where_clause: Option<&'a syn::WhereClause>,
+    turbofish: syn::Turbofish<'a>,
+    generic_scope: TS2,
+    nested_generic_scope: TS2,
+}
+
+impl<'a> Table<'a> {
+    fn new(ast: &'a DeriveInput, attrs: TableAttrs) -> deluxe::Result<Self> {
+        let node = &ast.ident;
+
+        // Carry the model's generics through every generated impl and function
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+        let turbofish = ty_generics.as_turbofish();
+
+        // Bounds of generic models may name items of the model's module
+        let (generic_scope, nested_generic_scope) = match ast.generics.params.is_empty() {
+            true => (quote::quote!{}, quote::quote!{}),
+            false => (quote::quote!(use super::*;), quote::quote!(use super::super::*;)),
+        };
+
+        // Create table name
+        let table_name = derive_utils::derive_snake_case(attrs.rename
+            .as_ref()
+            .map(|s| s.value())
+            .unwrap_or(node.to_string()));
+
+        let aliases = match &attrs.alias {
+            Some(alias) => derive_aliases(alias)?,
+            None => Vec::<String>::new()  // Specify type explicitly
+        };
+
+        // Create driver, any generates an additional module over sqlx::Any
+        let is_any = match &attrs.driver {
+            Some(driver) => match driver.value().as_str() {
+                "postgres" => false,
+                "any" => true,
+                value => return Err(syn::Error::new(
+                    driver.span(),
+                    format!("unknown driver `{}`, expected one of: postgres, any", value)
+                ))
             },
-            "Vec<String>" => {
-                all_setters.push(quote::quote! {
-                    pub fn #setter_name<T: ToString>(mut self, value: Vec<T>) -> Self {
-                        let value: Vec<String> = value
-                            .into_iter()
-                            .map(|v| v.to_string())
-                            .filter(|s| !s.is_empty())
-                            .collect();
+            None => false
+        };
 
-                        self.#field = nulls::new(value);
+        // Create generated blocks
+        let generates = derive_generates(&attrs.generate, &attrs.skip_generate)?;
 
-                        self
-                    }
-                });
+        // Create sqlcommenter trace context appended to generated statements, opted into per model
+        // as the traceparent makes every statement text unique, bypassing sqlx's prepared statement cache
+        let commenter = match cfg!(feature = "sqlcommenter") && attrs.sqlcommenter.is_set() {
+            true => quote::quote! {
+                let sql = format!("{}{}", sql, #node #turbofish::sql_comment());
             },
-            _ => {
-                all_setters.push(quote::quote! {
-                    pub fn #setter_name(mut self, value: #inner_ty) -> Self {
-                        self.#field = nulls::new(value);
+            false => quote::quote! {}
+        };
+
+        // Create error module path, defaults to the responder crate
+        let responder = match &attrs.responder {
+            Some(path) => path.parse::<syn::Path>()?,
+            None => syn::parse_quote!(responder)
+        };
+
+        // Create query observer, generated calls are instrumented when traced or observed
+        let metrics = match &attrs.metrics {
+            Some(path) => Some(path.parse::<syn::Path>()?),
+            None => None
+        };
+        let instrumented = cfg!(feature = "tracing") || metrics.is_some();
+
+        // Create statement policy, generated calls are guarded by a timeout and bounded retries when set
+        let timeout_ms = match &attrs.timeout_ms {
+            Some(timeout_ms) => Some(timeout_ms.base10_parse::<u64>()?),
+            None => None
+        };
+        let retries = match &attrs.retries {
+            Some(retries) => retries.base10_parse::<u32>()?,
+            None => 0
+        };
+        let execution = Execution { instrumented, guarded: timeout_ms.is_some() || retries > 0 };
+
+        // Create nulls crate path, defaults to the nulls crate
+        let nulls = match &attrs.nulls {
+            Some(path) => path.parse::<syn::Path>()?,
+            None => syn::parse_quote!(nulls)
+        };
+
+        // Create error messages
+        let error = format!("No matching record(s) found in {} table", table_name);
+        let unchanged = format!("No modified columns to update in {} table", table_name);
+        let db_error = format_ident!("{}DbError", node);
+
+        // Retrieve all fields once
+        let fields = derive_utils::derive_try_all_fields::<&str, ColumnAttrs>(ast, "column")?;
+
+        // Models declaring a `touched: derives::Touched` field track the fields changed through setters
+        let is_tracked = fields.iter().any(|(field, _, is_attributed, _)| field == "touched" && !is_attributed);
+
+        let columns = fields
+            .into_iter()
+            .map(|(field, ty, is_attributed, attrs)| Column::new(ast, &table_name, field, ty, is_attributed, attrs))
+            .collect::<deluxe::Result<Vec<Column>>>()?;
+
+        // Create tenant scoping, every update, search and builder is filtered by it
+        let tenant = match &attrs.tenant {
+            Some(name) => match columns.iter().position(|c| c.field == name.value() && c.is_attributed) {
+                Some(position) => Some(position),
+                None => return Err(syn::Error::new(
+                    name.span(),
+                    format!("tenant `{}` must name a #[column] field of {}", name.value(), node)
+                ))
+            },
+            None => None
+        };
+
+        // Create returning columns, writes return every column unless narrowed with #[table(returning)]
+        let attributed = columns.iter().filter(|c| c.is_attributed).collect::<Vec<&Column>>();
+        let (returning, returned) = match &attrs.returning {
+            Some(returning) => {
+                let mut aliased = Vec::<&str>::new();
+                let mut fields = Vec::<&Ident>::new();
+
+                for field in returning.value().split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                    let Some(column) = attributed.iter().find(|c| c.field == field) else {
+                        return Err(syn::Error::new(
+                            returning.span(),
+                            format!("returning field `{}` is not a column of {}", field, node)
+                        ));
+                    };
+
+                    aliased.push(&column.aliased);
+                    fields.push(&column.field);
+                }
 
-                        self
-                    }
-                });
-            }
+                (aliased.join(", "), quote::quote! {
+                    .map(|returned| {
+                        let mut record = self.clone();
+
+                        #(
+                            record.#fields = returned.#fields;
+                        )*
+
+                        record
+                    })
+                })
+            },
+            None => (attributed.iter().map(|c| c.aliased.as_str()).collect::<Vec<&str>>().join(", "), quote::quote! {})
+        };
+
+        Ok(Self {
+            ast,
+            node,
+            attrs,
+            table_name,
+            aliases,
+            generates,
+            is_any,
+            commenter,
+            responder,
+            nulls,
+            metrics,
+            timeout_ms,
+            retries,
+            execution,
+            error,
+            unchanged,
+            db_error,
+            is_tracked,
+            columns,
+            tenant,
+            returning,
+            returned,
+            impl_generics,
+            ty_generics,
+            where_clause,
+            turbofish,
+            generic_scope,
+            nested_generic_scope,
+        })
+    }
+
+    // Checks whether a block is generated
+    fn generates(&self, block: &str) -> bool {
+        self.generates.contains(&block)
+    }
+
+    // Columns read from the table, in declaration order
+    fn attributed(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter().filter(|c| c.is_attributed)
+    }
+
+    // Columns bound by inserts, timestamps are stamped by the database instead
+    fn inserted(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter().filter(|c| c.is_writable() && !c.is_timestamp())
+    }
+
+    // Timestamp columns set to now() by inserts
+    fn insert_timestamps(&self) -> Vec<&str> {
+        self.columns
+            .iter()
+            .filter(|c| c.is_writable() && c.is_timestamp())
+            .map(|c| c.plain.as_str())
+            .collect()
+    }
+
+    // Columns bound by updates
+    fn updated(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter().filter(|c| c.is_updatable())
+    }
+
+    // Assignments of the timestamp columns stamped by updates
+    fn update_timestamps(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter(|c| c.is_stamped())
+            .map(|c| format!("{} = now()", c.plain))
+            .collect()
+    }
+
+    // The `id` field, whose presence enables the blocks addressing single records
+    fn id(&self) -> Option<&Column> {
+        self.columns.iter().find(|c| c.field == "id")
+    }
+
+    // The optimistic lock bumped by every update
+    fn version(&self) -> Option<&Column> {
+        self.columns.iter().rfind(|c| c.attrs.version.is_set())
+    }
+
+    // The column every update, search and builder is scoped by
+    fn tenant(&self) -> Option<&Column> {
+        self.tenant.map(|position| &self.columns[position])
+    }
+
+    // Create the expression checking whether an update leaves the column of `owner` out,
+    // tracked models only update the fields their setters touched
+    fn untouched(&self, column: &Column, owner: TS2) -> TS2 {
+        let field = &column.field;
+
+        match self.is_tracked {
+            true => quote::quote!(!#owner.touched.contains(stringify!(#field))),
+            false => column.kind.undefined(quote::quote!(#owner.#field))
         }
+    }
+}
+
+// A field of the model with the names its column is read and written under
+struct Column {
+    field: Ident,
+    ty: Type,
+    kind: FieldKind,
+    inner_ty: Type,
+    inner_ty_str: String,
+    element_kind: FieldKind,
+    codec: Codec,
+    parse_with: Option<syn::Path>,
+    attrs: ColumnAttrs,
+    api: String,
+    plain: String,
+    selected: String,
+    renamed: String,
+    tabled: String,
+    aliased: String,
+    is_attributed: bool,
+    is_any: bool,
+}
 
+impl Column {
+    fn new(ast: &DeriveInput, table_name: &str, field: Ident, ty: Type, is_attributed: bool, attrs: ColumnAttrs) -> deluxe::Result<Self> {
+        let kind = derive_utils::derive_field_kind(&ty);
+        let inner_ty = kind.inner().clone();
+        let inner_ty_str = derive_utils::derive_normalize_type(&inner_ty);
+
+        // Arrays map to postgres array columns, except bytes which map to bytea
+        let element_kind = derive_utils::derive_field_kind(&inner_ty);
+
+        // Set how values are decoded from rows and bound to queries
+        let codec = derive_codec(&field, &attrs)?;
+        let parse_with = match &attrs.parse_with {
+            Some(path) => Some(path.parse::<syn::Path>()?),
+            None => None
+        };
+
+        // Set whether the column can be read and written through sqlx::Any
+        let is_any = parse_with.is_none() && (codec == Codec::Enum || matches!(
+            inner_ty_str.as_str(),
+            "bool" | "i16" | "i32" | "i64" | "f32" | "f64" | "String" | "Vec<u8>"
+        ));
+
+        // Columns follow serde renames of the field unless decoupled with #[column(rename)]
+        let api = derive_utils::derive_serde_name(ast, &field);
+        let plain = derive_column(ast, &field, &attrs);
+        let renamed = format!("{}_{}", table_name, plain);
+
+        // Computed columns are read through their expression under the column's name
+        let (tabled, selected) = match &attrs.computed {
+            Some(expression) => (
+                format!("({})", expression.value()),
+                format!("({}) AS {}", expression.value(), plain)
+            ),
+            None => (format!("{}.{}", table_name, plain), plain.clone())
+        };
+        let aliased = format!("{} AS {}", tabled, renamed);
+
+        // Ignored fields only exist in memory and are never read or written
+        let is_attributed = is_attributed && !attrs.ignore.is_set();
+
+        Ok(Self {
+            field,
+            ty,
+            kind,
+            inner_ty,
+            inner_ty_str,
+            element_kind,
+            codec,
+            parse_with,
+            attrs,
+            api,
+            plain,
+            selected,
+            renamed,
+            tabled,
+            aliased,
+            is_attributed,
+            is_any,
+        })
+    }
 
-        if field.to_string().as_str() == "id" {
-            let setter_name = format_ident!("set_insert_id");
-            all_setters.push(quote::quote!{
-                pub fn #setter_name<T>(mut self, size: T) -> Self
-                where
-                    T: ToString
-                {
-                    let size = size.to_string();
-                    let id = self.id().unwrap_or_default();
-
-                    if id.is_empty() {
-                        let id = match size.to_lowercase().as_str() {
-                            "sm" => ids::sm(),
-                            "md" => ids::md(),
-                            "lg" => ids::lg(),
-                            _ => ids::max(),
-                        };
+    fn is_array(&self) -> bool {
+        matches!(self.element_kind, FieldKind::Vec(_))
+    }
 
-                        self.id = nulls::new(id.to_string());
-                    }
+    fn is_timestamp(&self) -> bool {
+        self.attrs.created_at.is_set() || self.attrs.updated_at.is_set()
+    }
 
-                    self
-                }
-            });
+    // Search vectors and computed columns are maintained by the database
+    fn is_writable(&self) -> bool {
+        self.is_attributed && !self.attrs.tsvector.is_set() && self.attrs.computed.is_none()
+    }
+
+    fn is_stamped(&self) -> bool {
+        self.is_attributed && self.attrs.updated_at.is_set()
+    }
+
+    fn is_updatable(&self) -> bool {
+        !self.is_stamped()
+            && self.field != "id"
+            && self.is_writable()
+            && !self.attrs.version.is_set()
+            && !self.attrs.skip_update.is_set()
+            && !self.attrs.readonly.is_set()
+            && !self.is_timestamp()
+    }
+
+    // Readonly columns, search vectors, computed, ignored and opted out fields have no setters
+    fn is_settable(&self) -> bool {
+        !self.attrs.readonly.is_set()
+            && !self.attrs.tsvector.is_set()
+            && self.attrs.computed.is_none()
+            && !self.attrs.ignore.is_set()
+            && !self.attrs.no_setter.is_set()
+    }
+
+    // Constants are named after the field so they stay put when columns are renamed
+    fn constant(&self) -> String {
+        derive_utils::derive_snake_case(&self.field).to_uppercase()
+    }
+
+    fn decoder(&self, column: &str, nulls: &syn::Path) -> TS2 {
+        derive_decoder(&self.kind, self.codec, self.parse_with.as_ref(), column, nulls)
+    }
+
+    fn column_decoder(&self, column: TS2, nulls: &syn::Path) -> TS2 {
+        derive_column_decoder(&self.kind, self.codec, self.parse_with.as_ref(), column, nulls)
+    }
+
+    // Create the expression binding the field through the getter of `owner`
+    fn encoder(&self, owner: TS2) -> TS2 {
+        let field = &self.field;
+
+        derive_encoder(quote::quote!(#owner.#field()), self.codec, true)
+    }
+}
+
+// Create Constant Sub-modules
+fn derive_constants(table: &Table) -> TS2 {
+    let columns = table.attributed().collect::<Vec<&Column>>();
+    let all_const_names = columns.iter().map(|c| format_ident!("{}", c.constant())).collect::<Vec<Ident>>();
+    let all_aliased = columns.iter().map(|c| c.aliased.as_str()).collect::<Vec<&str>>();
+    let all_plain = columns.iter().map(|c| c.selected.as_str()).collect::<Vec<&str>>();
+    let all_api = columns.iter().map(|c| c.api.as_str()).collect::<Vec<&str>>();
+    let all_renamed = columns.iter().map(|c| c.renamed.as_str()).collect::<Vec<&str>>();
+    let all_tabled = columns.iter().map(|c| c.tabled.as_str()).collect::<Vec<&str>>();
+    let (all_aliased_str, all_plain_str) = (all_aliased.join(", "), all_plain.join(", "));
+    let (all_api_str, all_renamed_str, all_tabled_str) = (all_api.join(", "), all_renamed.join(", "), all_tabled.join(", "));
+
+    // Enum columns expose their allowed values
+    let enums = columns.iter().copied().filter(|c| c.codec == Codec::Enum).collect::<Vec<&Column>>();
+    let all_enum_consts = enums.iter().map(|c| format_ident!("{}", c.field.to_string().to_uppercase())).collect::<Vec<Ident>>();
+    let all_enum_types = enums.iter().map(|c| &c.inner_ty).collect::<Vec<&Type>>();
+
+    // Create window aggregates selected next to the columns, totals ride along paginated rows
+    let (all_aliased_total_str, all_plain_total_str) = match columns.is_empty() {
+        true => (TOTAL_COUNT.to_string(), TOTAL_COUNT.to_string()),
+        false => (
+            format!("{}, {}", all_aliased_str, TOTAL_COUNT),
+            format!("{}, {}", all_plain_str, TOTAL_COUNT)
+        )
+    };
+
+    // Create Sub Alias
+    //____________________________________________________________
+    let sub_alias = derive_alias_columns(table).into_iter().map(|(alias, columns)| {
+        let v = columns.iter().map(|c| format!("{} AS {}_{}", c.tabled, alias, c.plain)).collect::<Vec<String>>();
+        let all_alias_str = v.join(", ");
+        let module = format_ident!("{}", alias);
+
+        quote::quote!{
+            pub mod #module {
+                pub const ALL: &'static str = #all_alias_str;
+
+                #(
+                    pub const #all_const_names: &'static str = #v;
+                )*
+            }
         }
+    });
 
-        // All clones
-        let clone_name = format_ident!("clone_{}", field.clone());
-        match ty_to_str.to_lowercase().starts_with("string") {
-            true => all_clones.push(quote::quote! {
-                pub fn #clone_name(mut self, value: #ty) -> Self {
-                    self.#field = value;
+    quote::quote!{
+        pub mod alias {
+            pub const ALL: &'static str = #all_aliased_str;
 
-                    self
-                }
-            }),
-            false => all_clones.push(quote::quote! {
-                pub fn #clone_name(mut self, value: &#ty) -> Self {
-                    self.#field = value.clone();
+            #(
+                pub const #all_const_names: &'static str = #all_aliased;
+            )*
 
-                    self
-                }
-            })
+
+            #(#sub_alias)*
         }
 
-        // All Null ⟶ Undefined
-        let clear_name = format_ident!("clear_{}", field.clone());
-        if ty_to_str.to_lowercase().starts_with("null<") {
-            all_cleable_fields.push(field.clone());
+        pub mod plain {
+            pub const ALL: &'static str = #all_plain_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_plain;
+            )*
+        }
+
+        pub mod api {
+            pub const ALL: &'static str = #all_api_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_api;
+            )*
+        }
+
+        pub mod renamed {
+            pub const ALL: &'static str = #all_renamed_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_renamed;
+            )*
+        }
+
+        pub mod tabled {
+            pub const ALL: &'static str = #all_tabled_str;
+
+            #(
+                pub const #all_const_names: &'static str = #all_tabled;
+            )*
+        }
+
+        pub mod values {
+            #(
+                pub const #all_enum_consts: &'static [&'static str] = <#all_enum_types>::VALUES;
+            )*
+        }
+
+        pub mod agg {
+            pub const TOTAL_COUNT_COLUMN: &'static str = "total_count";
+            pub const TOTAL_COUNT: &'static str = #TOTAL_COUNT;
+            pub const ALIAS_ALL_WITH_TOTAL: &'static str = #all_aliased_total_str;
+            pub const PLAIN_ALL_WITH_TOTAL: &'static str = #all_plain_total_str;
+        }
+    }
+}
+
+// Window aggregate counting every matching row next to a page of them
+const TOTAL_COUNT: &str = "COUNT(*) OVER() AS total_count";
+
+// Pair every alias with the columns it is selected under, none when the model has no columns
+fn derive_alias_columns<'a>(table: &'a Table) -> Vec<(&'a str, Vec<&'a Column>)> {
+    let columns = table.attributed().collect::<Vec<&Column>>();
+
+    match columns.is_empty() {
+        true => Vec::new(),
+        false => table.aliases.iter().map(|alias| (alias.as_str(), columns.clone())).collect()
+    }
+}
+
+// Create Order Sub-module
+fn derive_order(table: &Table) -> TS2 {
+    let columns = table.attributed().collect::<Vec<&Column>>();
+    let all_order_asc = columns.iter().map(|c| format_ident!("{}_ASC", c.constant())).collect::<Vec<Ident>>();
+    let all_order_desc = columns.iter().map(|c| format_ident!("{}_DESC", c.constant())).collect::<Vec<Ident>>();
+    let all_order_asc_sql = columns.iter().map(|c| format!("{} ASC", c.tabled)).collect::<Vec<_>>();
+    let all_order_desc_sql = columns.iter().map(|c| format!("{} DESC", c.tabled)).collect::<Vec<_>>();
+
+    // Create sort variants, parsed from the serialized names of their columns
+    let all_sort_asc = columns.iter().map(|c| format_ident!("{}Asc", derive_utils::derive_pascal_case(&c.field))).collect::<Vec<Ident>>();
+    let all_sort_desc = columns.iter().map(|c| format_ident!("{}Desc", derive_utils::derive_pascal_case(&c.field))).collect::<Vec<Ident>>();
+    let sort_key = |api: &String| api.to_lowercase().replace(['_', '-'], "");
+    let all_sort_asc_keys = columns.iter().map(|c| format!("{}asc", sort_key(&c.api))).collect::<Vec<_>>();
+    let all_sort_desc_keys = columns.iter().map(|c| format!("{}desc", sort_key(&c.api))).collect::<Vec<_>>();
+
+    quote::quote!{
+        pub mod order {
+            #(
+                pub const #all_order_asc: &'static str = #all_order_asc_sql;
+                pub const #all_order_desc: &'static str = #all_order_desc_sql;
+            )*
+
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum Sort {
+                #(#all_sort_asc, #all_sort_desc,)*
+            }
+
+            impl Sort {
+                pub fn to_sql(&self) -> &'static str {
+                    match self {
+                        #(
+                            Self::#all_sort_asc => #all_order_asc,
+                            Self::#all_sort_desc => #all_order_desc,
+                        )*
+                    }
+                }
+
+                pub fn from_parts<T, U>(column: T, order: U) -> Option<Self>
+                where
+                    T: ToString,
+                    U: ToString
+                {
+                    format!("{}_{}", column.to_string(), order.to_string()).parse().ok()
+                }
+            }
+
+            impl std::str::FromStr for Sort {
+                type Err = String;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    let key = value.to_lowercase().replace(['_', '-', ' ', ':'], "");
+
+                    match key.as_str() {
+                        #(
+                            #all_sort_asc_keys => Ok(Self::#all_sort_asc),
+                            #all_sort_desc_keys => Ok(Self::#all_sort_desc),
+                        )*
+                        _ => Err(format!("Unknown sort `{}`", value))
+                    }
+                }
+            }
+
+            impl std::fmt::Display for Sort {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    write!(f, "{}", self.to_sql())
+                }
+            }
+
+            impl<'de> serde::de::Deserialize<'de> for Sort {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    let value = String::deserialize(deserializer)?;
+
+                    value.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        }
+    }
+}
+
+// Create Query Builder Sub-module
+fn derive_query(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, commenter, turbofish, impl_generics, ty_generics, where_clause, generic_scope, .. } = table;
+    let all_filters = table.attributed().map(derive_filters).collect::<Vec<TS2>>();
+    let builder_rows = derive_traced(table.execution, quote::quote!(#node #turbofish), "select", quote::quote!(&sql), quote::quote! {
+        sqlx::query_with(&sql, arguments.clone()).fetch_all(database::writer())
+    });
+
+    // Tenant scoped builders can only be started from the tenant condition
+    let builder_new = match table.tenant() {
+        Some(tenant) => {
+            let filter_eq = format_ident!("{}_eq", tenant.field);
+            let tenant_ty = &tenant.inner_ty;
+
+            quote::quote! {
+                pub fn new(tenant: impl Into<#tenant_ty>) -> Self {
+                    Self::unscoped().#filter_eq(tenant)
+                }
+            }
+        },
+        None => quote::quote! {
+            pub fn new() -> Self {
+                Self::unscoped()
+            }
+        }
+    };
+    let builder_default = match table.tenant() {
+        Some(_) => quote::quote! {},
+        None => quote::quote! {
+            impl #impl_generics Default for Builder #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self::unscoped()
+                }
+            }
+        }
+    };
+
+    quote::quote!{
+        pub mod query {
+            use sqlx::Arguments;
+
+            use super::#node;
+            #generic_scope
+
+            pub struct Builder #impl_generics #where_clause {
+                conditions: Vec<String>,
+                arguments: sqlx::postgres::PgArguments,
+                error: Option<sqlx::error::BoxDynError>,
+                marker: std::marker::PhantomData<fn() -> #node #ty_generics>,
+            }
+
+            #builder_default
+
+            impl #impl_generics Builder #ty_generics #where_clause {
+                fn unscoped() -> Self {
+                    Self {
+                        conditions: Vec::new(),
+                        arguments: Default::default(),
+                        error: None,
+                        marker: std::marker::PhantomData,
+                    }
+                }
+
+                #builder_new
+
+                fn bind<Value>(mut self, condition: &str, value: Value) -> Self
+                where
+                    Value: for<'q> sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Send + 'static
+                {
+                    if let Err(e) = self.arguments.add(value) {
+                        self.error.get_or_insert(e);
+                    }
+
+                    let placeholder = format!("${}", self.arguments.len());
+                    self.conditions.push(condition.replace("{}", &placeholder));
+
+                    self
+                }
+
+                #(#all_filters)*
+
+                pub fn where_clause(&self) -> String {
+                    match self.conditions.is_empty() {
+                        true => String::new(),
+                        false => format!("WHERE {}", self.conditions.join(" AND "))
+                    }
+                }
+
+                fn select(&self, columns: &str) -> String {
+                    format!("SELECT {} FROM {} {}", columns, #table_name, self.where_clause())
+                        .trim()
+                        .to_string()
+                }
+
+                pub fn sql(&self) -> String {
+                    self.select(#node #turbofish::ALIAS_ALL)
+                }
+
+                pub fn build(self) -> sqlx::Result<(String, sqlx::postgres::PgArguments)> {
+                    let sql = self.sql();
+
+                    match self.error {
+                        Some(e) => Err(sqlx::Error::Encode(e)),
+                        None => Ok((sql, self.arguments))
+                    }
+                }
+
+                pub async fn fetch_all(self) -> #responder::Result<Vec<#node #ty_generics>> {
+                    let (sql, arguments) = self.build().map_err(#responder::query)?;
+                    #commenter
+                    let rows = #builder_rows.map_err(#responder::query)?;
+
+                    Ok(rows.iter().map(#node #turbofish::parse).collect())
+                }
+
+                /// Fetches one page of the matching records along with the count of every match.
+                ///
+                /// # Arguments
+                /// - `order_by`: The ordering, e.g. a constant of the `order` module.
+                /// - `limit`: The maximum number of records to return.
+                /// - `offset`: The number of records to skip.
+                pub async fn fetch_page(self, order_by: &'static str, limit: i64, offset: i64) -> #responder::Result<(Vec<#node #ty_generics>, i64)> {
+                    let columns = format!("{}, {}", #node #turbofish::ALIAS_ALL, #TOTAL_COUNT);
+                    let sql = format!("{} ORDER BY {} LIMIT {} OFFSET {}", self.select(&columns), order_by, limit.max(0), offset.max(0));
+                    let (_, arguments) = self.build().map_err(#responder::query)?;
+                    #commenter
+                    let rows = #builder_rows.map_err(#responder::query)?;
+                    let total = rows.first().map(|row| #node #turbofish::parse_with_total(row).1).unwrap_or_default();
+
+                    Ok((rows.iter().map(#node #turbofish::parse).collect(), total))
+                }
+            }
+        }
+    }
+}
+
+// Create the query builder filters of a column
+fn derive_filters(column: &Column) -> TS2 {
+    let Column { field, inner_ty, tabled, codec, .. } = column;
+
+    let filter_eq = format_ident!("{}_eq", field);
+    let filter_ne = format_ident!("{}_ne", field);
+    let filter_is_null = format_ident!("{}_is_null", field);
+    let filter_is_not_null = format_ident!("{}_is_not_null", field);
+    let value = derive_encoder(quote::quote!(Into::<#inner_ty>::into(value)), *codec, false);
+    let from = derive_encoder(quote::quote!(Into::<#inner_ty>::into(from)), *codec, false);
+    let to = derive_encoder(quote::quote!(Into::<#inner_ty>::into(to)), *codec, false);
+
+    let mut filters = quote::quote! {
+        pub fn #filter_eq<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+            self.bind(concat!(#tabled, " = {}"), #value)
+        }
+
+        pub fn #filter_ne<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+            self.bind(concat!(#tabled, " <> {}"), #value)
+        }
+
+        pub fn #filter_is_null(mut self) -> Self {
+            self.conditions.push(concat!(#tabled, " IS NULL").to_string());
+
+            self
+        }
+
+        pub fn #filter_is_not_null(mut self) -> Self {
+            self.conditions.push(concat!(#tabled, " IS NOT NULL").to_string());
+
+            self
+        }
+    };
+
+    match (column.is_array(), codec) {
+        (_, Codec::Json) => {
+            let filter_contains = format_ident!("{}_contains", field);
+
+            filters.extend(quote::quote! {
+                pub fn #filter_contains<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+                    self.bind(concat!(#tabled, " @> {}"), #value)
+                }
+            });
+        },
+        (true, _) => {
+            let element_ty = column.element_kind.inner();
+            let filter_any = format_ident!("{}_any", field);
+            let filter_contains = format_ident!("{}_array_contains", field);
+            let filter_overlaps = format_ident!("{}_overlaps", field);
+
+            filters.extend(quote::quote! {
+                pub fn #filter_any<Value: Into<#element_ty>>(self, value: Value) -> Self {
+                    self.bind(concat!("{} = ANY(", #tabled, ")"), value.into())
+                }
+
+                pub fn #filter_contains<Value: Into<#element_ty> + Clone>(self, values: &[Value]) -> Self {
+                    let values: #inner_ty = values.iter().cloned().map(Into::into).collect();
+
+                    self.bind(concat!(#tabled, " @> {}"), values)
+                }
+
+                pub fn #filter_overlaps<Value: Into<#element_ty> + Clone>(self, values: &[Value]) -> Self {
+                    let values: #inner_ty = values.iter().cloned().map(Into::into).collect();
+
+                    self.bind(concat!(#tabled, " && {}"), values)
+                }
+            });
+        },
+        (false, _) => {
+            let filter_lt = format_ident!("{}_lt", field);
+            let filter_gt = format_ident!("{}_gt", field);
+            let filter_in = format_ident!("{}_in", field);
+            let filter_between = format_ident!("{}_between", field);
+
+            let filter_in_method = match codec {
+                Codec::Enum => quote::quote! {
+                    pub fn #filter_in<Value: Into<#inner_ty> + Clone>(self, values: &[Value]) -> Self {
+                        let values = values
+                            .iter()
+                            .cloned()
+                            .map(|v| Into::<#inner_ty>::into(v).to_string())
+                            .collect::<Vec<String>>();
+
+                        self.bind(concat!(#tabled, " = ANY({})"), values)
+                    }
+                },
+                _ => quote::quote! {
+                    pub fn #filter_in<Value>(self, values: &[Value]) -> Self
+                    where
+                        Value: Clone + sqlx::postgres::PgHasArrayType + for<'q> sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Send + 'static,
+                        #inner_ty: From<Value>
+                    {
+                        self.bind(concat!(#tabled, " = ANY({})"), values.to_vec())
+                    }
+                }
+            };
+
+            filters.extend(quote::quote! {
+                pub fn #filter_lt<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+                    self.bind(concat!(#tabled, " < {}"), #value)
+                }
+
+                pub fn #filter_gt<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+                    self.bind(concat!(#tabled, " > {}"), #value)
+                }
+
+                #filter_in_method
+
+                pub fn #filter_between<Value: Into<#inner_ty>>(self, from: Value, to: Value) -> Self {
+                    self.bind(concat!(#tabled, " >= {}"), #from)
+                        .bind(concat!(#tabled, " <= {}"), #to)
+                }
+            });
+
+            // Time columns also filter on half-open ranges, as list endpoints usually do
+            let is_time = column.inner_ty_str.starts_with("DateTime<")
+                || matches!(column.inner_ty_str.as_str(), "NaiveDateTime" | "NaiveDate");
+
+            if is_time {
+                let filter_since = format_ident!("{}_since", field);
+                let filter_until = format_ident!("{}_until", field);
+                let filter_range = format_ident!("{}_range", field);
+
+                filters.extend(quote::quote! {
+                    pub fn #filter_since<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+                        self.bind(concat!(#tabled, " >= {}"), #value)
+                    }
+
+                    pub fn #filter_until<Value: Into<#inner_ty>>(self, value: Value) -> Self {
+                        self.bind(concat!(#tabled, " < {}"), #value)
+                    }
+
+                    pub fn #filter_range<Value: Into<#inner_ty>>(self, from: Option<Value>, to: Option<Value>) -> Self {
+                        let builder = match from {
+                            Some(from) => self.#filter_since(from),
+                            None => self
+                        };
+
+                        match to {
+                            Some(to) => builder.#filter_until(to),
+                            None => builder
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    filters
+}
+
+// Create Error implementation
+fn derive_db_error(table: &Table) -> TS2 {
+    let Table { table_name, responder, error, unchanged, db_error, .. } = table;
+    let stale = format!("Record in {} table was modified by another transaction", table_name);
+    let conflict = format!("Record conflicts with an existing record in {} table", table_name);
+
+    // Violations are recognized by postgres' default constraint names, unique ones also by the offending key
+    let unique_violations = table.attributed()
+        .filter_map(|c| c.attrs.unique.as_ref().map(|message| {
+            let constraint = format!("{}_{}_key", table_name, c.plain);
+            let key = format!("Key ({})=", c.plain);
+
+            (quote::quote!(constraint == #constraint || detail.starts_with(#key)), message.value())
+        }))
+        .collect::<Vec<(TS2, String)>>();
+    let check_violations = table.attributed()
+        .filter_map(|c| c.attrs.check.as_ref().map(|message| {
+            let constraint = format!("{}_{}_check", table_name, c.plain);
+
+            (quote::quote!(constraint == #constraint), message.value())
+        }))
+        .collect::<Vec<(TS2, String)>>();
+
+    // Declared constraints turn into their own messages, other unique violations stay conflicts
+    let violation_arm = |violations: &[(TS2, String)], check: TS2, fallback: TS2| {
+        if violations.is_empty() {
+            return quote::quote! {};
+        }
+
+        let conditions = violations.iter().map(|(condition, _)| condition);
+        let messages = violations.iter().map(|(_, message)| message);
+
+        quote::quote! {
+            sqlx::Error::Database(d) if d.#check() => {
+                let constraint = d.constraint().unwrap_or_default();
+                let detail = d
+                    .try_downcast_ref::<sqlx::postgres::PgDatabaseError>()
+                    .and_then(|d| d.detail())
+                    .unwrap_or_default();
+
+                #(
+                    if #conditions {
+                        return Self::Violation(#messages);
+                    }
+                )*
+
+                #fallback
+            },
+        }
+    };
+    let unique_arm = violation_arm(&unique_violations, quote::quote!(is_unique_violation), quote::quote!(Self::Conflict));
+    let check_arm = violation_arm(&check_violations, quote::quote!(is_check_violation), quote::quote!(Self::Query(e)));
+
+    quote::quote!{
+        #[derive(Debug)]
+        pub enum #db_error {
+            NotFound,
+            Conflict,
+            Stale,
+            Unchanged,
+            Violation(&'static str),
+            Query(sqlx::Error),
+        }
+
+        impl std::fmt::Display for #db_error {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                match self {
+                    Self::NotFound => write!(f, #error),
+                    Self::Conflict => write!(f, #conflict),
+                    Self::Stale => write!(f, #stale),
+                    Self::Unchanged => write!(f, #unchanged),
+                    Self::Violation(message) => write!(f, "{}", message),
+                    Self::Query(e) => write!(f, "{}", e),
+                }
+            }
+        }
+
+        impl std::error::Error for #db_error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    Self::Query(e) => Some(e),
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<sqlx::Error> for #db_error {
+            fn from(e: sqlx::Error) -> Self {
+                match &e {
+                    sqlx::Error::RowNotFound => Self::NotFound,
+                    #unique_arm
+                    #check_arm
+                    sqlx::Error::Database(d) if d.is_unique_violation() => Self::Conflict,
+                    _ => Self::Query(e),
+                }
+            }
+        }
+
+        impl From<#db_error> for #responder::Error {
+            fn from(e: #db_error) -> Self {
+                match e {
+                    #db_error::Query(e) => #responder::query(e),
+                    e => #responder::to(e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+// Create Parser Sub-module
+fn derive_parsers(table: &Table) -> TS2 {
+    let Table { node, responder, nulls, db_error, turbofish, impl_generics, ty_generics, where_clause, generic_scope, nested_generic_scope, .. } = table;
+    let group_generics = derive_utils::derive_merge_generics(&table.ast.generics, parse_quote!(Key: ToString));
+
+    // Aliased rows parse through their own sub-module
+    let sub_parser_mod = derive_alias_columns(table).into_iter().map(|(alias, _)| {
+        let module = format_ident!("{}", alias);
+        let k = format_ident!("parse_{}", alias);
+
+        quote::quote!{
+            pub mod #module {
+                use #nulls::Null;
+                use sqlx::{Result, Row, postgres::PgRow};
+
+                use super::super::{#node, #db_error};
+                #nested_generic_scope
+
+                pub fn parse #impl_generics (row: &PgRow) -> #node #ty_generics #where_clause {
+                    #node #turbofish::#k(row)
+                }
+
+                pub fn result #impl_generics (row: Result<sqlx::postgres::PgRow>) -> #responder::Result<#node #ty_generics> #where_clause {
+                    try_result #turbofish(row).map_err(Into::into)
+                }
+
+                pub fn try_result #impl_generics (row: Result<sqlx::postgres::PgRow>) -> std::result::Result<#node #ty_generics, #db_error> #where_clause {
+                    let result = row?;
+                    let row = parse #turbofish(&result);
+
+                    match !row.is_empty() {
+                        true => Ok(row),
+                        false => Err(#db_error::NotFound)
+                    }
+                }
+
+                pub fn relational #impl_generics (row: &PgRow) -> Null<#node #ty_generics> #where_clause {
+                    let row = parse #turbofish(row);
+
+                    match row.is_empty() {
+                        true => #nulls::undefined(),
+                        false => #nulls::new(row)
+                    }
+                }
+
+                pub fn try_parse #impl_generics (row: &PgRow) -> Option<#node #ty_generics> #where_clause {
+                    let row = parse #turbofish(row);
+
+                    match row.is_empty() {
+                        true => None,
+                        false => Some(row)
+                    }
+                }
+
+                pub fn group_by #group_generics (rows: &[PgRow], parent_key: Key) -> Vec<(String, Vec<#node #ty_generics>)>
+                #where_clause
+                {
+                    let parent_key = parent_key.to_string();
+                    let mut indexes = std::collections::HashMap::<String, usize>::new();
+                    let mut groups = Vec::<(String, Vec<#node #ty_generics>)>::new();
+
+                    for row in rows {
+                        let Ok(key) = row.try_get::<String, &str>(&parent_key) else {
+                            continue;
+                        };
+
+                        let index = *indexes.entry(key.clone()).or_insert_with(|| {
+                            groups.push((key, Vec::new()));
+                            groups.len() - 1
+                        });
+
+                        if let Some(child) = try_parse #turbofish(row) {
+                            groups[index].1.push(child);
+                        }
+                    }
+
+                    groups
+                }
+            }
+        }
+    });
+
+    quote::quote!{
+        pub mod parsers {
+            use #nulls::Null;
+            use sqlx::{Result, Row, postgres::PgRow};
+
+            use super::{#node, #db_error};
+            #generic_scope
+
+            pub fn parse #impl_generics (row: &PgRow) -> #node #ty_generics #where_clause {
+                #node #turbofish::parse(row)
+            }
+
+            pub fn prefixed #impl_generics (row: &PgRow, prefix: &str) -> #node #ty_generics #where_clause {
+                #node #turbofish::parse_prefixed(row, prefix)
+            }
+
+            pub fn with_total #impl_generics (rows: &[PgRow]) -> (Vec<#node #ty_generics>, i64) #where_clause {
+                let total = rows
+                    .first()
+                    .map(|row| row.try_get::<i64, _>("total_count").unwrap_or_default())
+                    .unwrap_or_default();
+
+                (rows.iter().map(parse #turbofish).collect(), total)
+            }
+
+            pub fn result #impl_generics (row: Result<sqlx::postgres::PgRow>) -> #responder::Result<#node #ty_generics> #where_clause {
+                try_result #turbofish(row).map_err(Into::into)
+            }
+
+            pub fn try_result #impl_generics (row: Result<sqlx::postgres::PgRow>) -> std::result::Result<#node #ty_generics, #db_error> #where_clause {
+                let result = row?;
+                let row = parse #turbofish(&result);
+
+                match !row.is_empty() {
+                    true => Ok(row),
+                    false => Err(#db_error::NotFound)
+                }
+            }
+
+            pub fn relational #impl_generics (row: &PgRow) -> Null<#node #ty_generics> #where_clause {
+                let row = parse #turbofish(row);
+
+                match row.is_empty() {
+                    true => #nulls::undefined(),
+                    false => #nulls::new(row)
+                }
+            }
+
+            #(#sub_parser_mod)*
+        }
+    }
+}
+
+// Create the wrappers rows selected under an alias parse into, so a joined row
+// can hold both versions of the node
+fn derive_alias_wrappers(table: &Table) -> TS2 {
+    let Table { ast, node, turbofish, impl_generics, ty_generics, where_clause, .. } = table;
+    let row_generics = derive_utils::derive_merge_generics(&ast.generics, parse_quote!('r));
+    let vis = &ast.vis;
+
+    let sub_wrappers = derive_alias_columns(table).into_iter().map(|(alias, _)| {
+        let k = format_ident!("parse_{}", alias);
+        let wrapper = format_ident!("{}As{}", node, derive_utils::derive_pascal_case(alias));
+        let wrapper_doc = format!("{} parsed from the columns aliased as `{}`", node, alias);
+
+        quote::quote! {
+            #[doc = #wrapper_doc]
+            #vis struct #wrapper #impl_generics (pub #node #ty_generics) #where_clause;
+
+            impl #impl_generics #wrapper #ty_generics #where_clause {
+                pub fn parse(row: &sqlx::postgres::PgRow) -> Self {
+                    Self(#node #turbofish::#k(row))
+                }
+
+                pub fn into_inner(self) -> #node #ty_generics {
+                    self.0
+                }
+            }
+
+            impl #impl_generics std::ops::Deref for #wrapper #ty_generics #where_clause {
+                type Target = #node #ty_generics;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl #impl_generics std::ops::DerefMut for #wrapper #ty_generics #where_clause {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.0
+                }
+            }
+
+            impl #impl_generics From<#wrapper #ty_generics> for #node #ty_generics #where_clause {
+                fn from(value: #wrapper #ty_generics) -> Self {
+                    value.0
+                }
+            }
+
+            impl #row_generics sqlx::FromRow<'r, sqlx::postgres::PgRow> for #wrapper #ty_generics #where_clause {
+                fn from_row(row: &'r sqlx::postgres::PgRow) -> sqlx::Result<Self> {
+                    Ok(Self::parse(row))
+                }
+            }
+        }
+    });
+
+    quote::quote!{
+        #(#sub_wrappers)*
+    }
+}
+
+// Create Node Related implementations
+fn derive_node(table: &Table) -> deluxe::Result<TS2> {
+    let Table { node, table_name, returning, nulls, impl_generics, ty_generics, where_clause, .. } = table;
+    let columns = table.attributed().collect::<Vec<&Column>>();
+    let all_aliased_str = columns.iter().map(|c| c.aliased.as_str()).collect::<Vec<&str>>().join(", ");
+    let all_attributed_fields = columns.iter().map(|c| &c.field).collect::<Vec<&Ident>>();
+
+    // Ordinals follow the declared order of plain::ALL
+    let all_attributed_decoders = columns.iter().map(|c| c.decoder(&c.renamed, nulls)).collect::<Vec<TS2>>();
+    let all_prefixed_decoders = columns.iter()
+        .map(|c| {
+            let plain = &c.plain;
+
+            c.column_decoder(quote::quote!(format!("{}_{}", prefix, #plain).as_str()), nulls)
+        })
+        .collect::<Vec<TS2>>();
+    let all_ordinal_decoders = columns.iter()
+        .enumerate()
+        .map(|(ordinal, c)| c.column_decoder(quote::quote!(#ordinal), nulls))
+        .collect::<Vec<TS2>>();
+
+    // Create identity comparison
+    let same_identity = match table.id().is_some() {
+        true => quote::quote! {
+            pub fn same_identity(&self, other: &Self) -> bool {
+                self.id.is_some() && self.id == other.id
+            }
+        },
+        false => quote::quote! {}
+    };
+
+    let all_compared_fields = table.columns
+        .iter()
+        .filter(|c| !c.attrs.skip_compare.is_set() && !c.attrs.version.is_set() && !c.is_timestamp())
+        .map(|c| &c.field)
+        .collect::<Vec<&Ident>>();
+
+    // Set defaults of built records, ids through the configured strategy and timestamps to now
+    let build_id = match (table.id().filter(|id| id.is_settable()), table.attrs.id.is_some()) {
+        (None, _) => quote::quote!{},
+        (Some(_), true) => quote::quote!(self = self.set_insert_id();),
+        (Some(_), false) => quote::quote!(self = self.set_insert_id("max");),
+    };
+    let all_build_timestamps = table.columns
+        .iter()
+        .filter(|c| c.is_timestamp() && matches!(c.kind, FieldKind::Null(_) | FieldKind::Option(_)))
+        .map(|c| {
+            let field = &c.field;
+            let now = match c.inner_ty_str.contains("Naive") {
+                true => quote::quote!(chrono::Utc::now().naive_utc()),
+                false => quote::quote!(chrono::Utc::now().into()),
+            };
+            let undefined = c.kind.undefined(quote::quote!(self.#field));
+            let now = derive_wrap(&c.kind, now, nulls);
+
+            quote::quote! {
+                if #undefined {
+                    self.#field = #now;
+                }
+            }
+        })
+        .collect::<Vec<TS2>>();
+
+    // Private fields are left out of public JSON under their serialized name
+    let all_private_keys = table.columns
+        .iter()
+        .filter(|c| c.attrs.private.is_set())
+        .map(|c| c.api.as_str())
+        .collect::<Vec<&str>>();
+    let private_len = all_private_keys.len();
+
+    // Tracked models forget their changes, others unset the fields setters would update
+    let all_update_fields = table.updated().map(|c| &c.field).collect::<Vec<&Ident>>();
+    let all_update_undefined = table.updated().map(|c| table.untouched(c, quote::quote!(self))).collect::<Vec<TS2>>();
+    let untouch = match table.is_tracked {
+        true => quote::quote!(self.touched.clear();),
+        false => {
+            let all_update_unset = table.updated().map(|c| derive_unset(&c.kind, nulls));
+
+            quote::quote! {
+                #(
+                    self.#all_update_fields = #all_update_unset;
+                )*
+            }
+        }
+    };
+
+    // Tracked models touch every field holding a value, others already update those
+    let touch_all = match table.is_tracked {
+        true => {
+            let all_update_unsent = table.updated().map(|c| {
+                let field = &c.field;
+
+                c.kind.undefined(quote::quote!(self.#field))
+            });
+
+            quote::quote! {
+                #(
+                    if !#all_update_unsent {
+                        self.touched.touch(stringify!(#all_update_fields));
+                    }
+                )*
+            }
+        },
+        false => quote::quote!()
+    };
+
+    // All Null ⟶ Undefined
+    let all_cleable_fields = table.columns
+        .iter()
+        .filter(|c| c.kind.is_null())
+        .map(|c| &c.field)
+        .collect::<Vec<&Ident>>();
+
+    let accessors = derive_accessors(table)?;
+    let sub_parsers = derive_alias_parsers(table);
+    let relations = derive_relations(table)?;
+    let searches = derive_searches(table);
+    let enum_validators = derive_enum_validators(table);
+
+    Ok(quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub const TABLE: &'static str = #table_name;
+            pub const ALIAS_ALL: &'static str = #all_aliased_str;
+            pub const RETURNING: &'static str = #returning;
+
+            pub fn is_empty(&self) -> bool {
+                *self == Self::default()
+            }
+
+            #same_identity
+
+            pub fn same_content(&self, other: &Self) -> bool {
+                true #(&& self.#all_compared_fields == other.#all_compared_fields)*
+            }
+
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn build(mut self) -> Self {
+                #build_id
+
+                #(#all_build_timestamps)*
+
+                self
+            }
+
+            pub fn diff(&self, other: &Self) -> Vec<(&'static str, serde_json::Value, serde_json::Value)> {
+                let mut changes = Vec::<(&'static str, serde_json::Value, serde_json::Value)>::new();
+
+                #(
+                    let old = serde_json::to_value(&self.#all_attributed_fields).unwrap_or_default();
+                    let new = serde_json::to_value(&other.#all_attributed_fields).unwrap_or_default();
+
+                    if old != new {
+                        changes.push((stringify!(#all_attributed_fields), old, new));
+                    }
+                )*
+
+                changes
+            }
+
+            pub fn to_json(&self) -> serde_json::Value {
+                serde_json::to_value(self)
+                    .unwrap_or(serde_json::Value::Null)
+            }
+
+            pub fn to_public_json(&self) -> serde_json::Value {
+                let mut value = self.to_json();
+                let private: [&str; #private_len] = [#(#all_private_keys),*];
+
+                if let serde_json::Value::Object(map) = &mut value {
+                    for key in private {
+                        map.remove(key);
+                    }
+                }
+
+                value
+            }
+
+            pub fn to_jsonb(&self) -> sqlx::types::Json<Self> {
+                sqlx::types::Json::from(self.clone())
+            }
+
+            #accessors
+
+            pub fn touched_fields(&self) -> Vec<&'static str> {
+                let mut fields = Vec::<&'static str>::new();
+
+                #(
+                    if !#all_update_undefined {
+                        fields.push(stringify!(#all_update_fields));
+                    }
+                )*
+
+                fields
+            }
+
+            pub fn untouched(mut self) -> Self {
+                #untouch
+
+                self
+            }
+
+            pub fn touch_all(mut self) -> Self {
+                #touch_all
+
+                self
+            }
+
+            pub fn clear_all(mut self) -> Self {
+                #(
+                    if !self.#all_cleable_fields.is_some() {
+                        self.#all_cleable_fields =  #nulls::undefined();
+                    }
+                )*
+
+                self
+            }
+
+            pub fn parse(row: &sqlx::postgres::PgRow) -> Self {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#all_attributed_fields = #all_attributed_decoders;
+                )*
+
+
+                data
+            }
+
+            pub fn parse_prefixed(row: &sqlx::postgres::PgRow, prefix: &str) -> Self {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#all_attributed_fields = #all_prefixed_decoders;
+                )*
+
+                data
+            }
+
+            pub fn parse_ordinal(row: &sqlx::postgres::PgRow) -> Self {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#all_attributed_fields = #all_ordinal_decoders;
+                )*
+
+                data
+            }
+
+            pub fn parse_with_total(row: &sqlx::postgres::PgRow) -> (Self, i64) {
+                use sqlx::Row;
+
+                let total = row.try_get::<i64, _>("total_count").unwrap_or_default();
+
+                (Self::parse(row), total)
+            }
+
+            #sub_parsers
+
+            #relations
+
+            #searches
+
+            #enum_validators
+        }
+    })
+}
+
+// Create props along with borrowing getters, setters, clones and clears of every field
+fn derive_accessors(table: &Table) -> deluxe::Result<TS2> {
+    let nulls = &table.nulls;
+    let mut all_props = Vec::<TS2>::with_capacity(table.columns.len());  // Specify types explicitly
+    let mut all_setters = Vec::<TS2>::with_capacity(table.columns.len());
+    let mut all_setter_opts = Vec::<TS2>::with_capacity(table.columns.len());
+    let mut all_clones = Vec::<TS2>::with_capacity(table.columns.len());
+    let mut all_clears = Vec::<TS2>::with_capacity(table.columns.len());
+
+    for column in &table.columns {
+        let Column { field, ty, kind, inner_ty, attrs, .. } = column;
+
+        // Tracked models record the fields their setters change
+        let touch = match table.is_tracked && column.is_updatable() {
+            true => quote::quote!(self.touched.touch(stringify!(#field));),
+            false => quote::quote!()
+        };
+
+        // Create props along with borrowing getters
+        let ref_name = format_ident!("{}_ref", field);
+        let vis = match &attrs.getters {
+            Some(getters) => getters.parse::<syn::Visibility>()?,
+            None => syn::parse_quote!(pub),
+        };
+
+        match kind {
+            FieldKind::Null(_) => all_props.push(quote::quote! {
+                #vis fn #field(&self) -> Option<#inner_ty> {
+                    self.#field.clone().take()
+                }
+
+                #vis fn #ref_name(&self) -> Option<&#inner_ty> {
+                    self.#field.as_ref().take()
+                }
+            }),
+            FieldKind::Option(_) => all_props.push(quote::quote! {
+                #vis fn #field(&self) -> Option<#inner_ty> {
+                    self.#field.clone()
+                }
+
+                #vis fn #ref_name(&self) -> Option<&#inner_ty> {
+                    self.#field.as_ref()
+                }
+            }),
+            _ => all_props.push(quote::quote! {
+                #vis fn #field(&self) -> #ty {
+                    self.#field.clone()
+                }
+
+                #vis fn #ref_name(&self) -> &#ty {
+                    &self.#field
+                }
+            })
+        }
+
+        // Create setters unless the column is readonly, a search vector, computed, ignored or opted out
+        if column.is_settable() {
+            // Values are stored through the field's own wrapper
+            let wrapped = derive_wrap(kind, quote::quote!(value), nulls);
+
+            // Create setter_opts
+            let setter_opt_name = format_ident!("set_opts_{}", field);
+            all_setter_opts.push(quote::quote! {
+                pub fn #setter_opt_name(mut self, value: &Option<#inner_ty>) -> Self {
+                    if let Some(value) = value.clone() {
+                        self.#field = #wrapped;
+                        #touch
+                    }
+
+                    self
+                }
+            });
+
+            // Create setters
+            let setter_name = format_ident!("set_{}", field);
+
+            match column.inner_ty_str.as_str() {
+                "String" => {
+                    all_setters.push(quote::quote! {
+                        pub fn #setter_name<Value: ToString>(mut self, value: Value) -> Self {
+                            let value = value.to_string();
+                            self.#field = #wrapped;
+                            #touch
+
+                            self
+                        }
+                    });
+                },
+                "Vec<String>" => {
+                    all_setters.push(quote::quote! {
+                        pub fn #setter_name<Value: ToString>(mut self, value: Vec<Value>) -> Self {
+                            let value: Vec<String> = value
+                                .into_iter()
+                                .map(|v| v.to_string())
+                                .filter(|s| !s.is_empty())
+                                .collect();
+
+                            self.#field = #wrapped;
+                            #touch
+
+                            self
+                        }
+                    });
+                },
+                _ => {
+                    all_setters.push(quote::quote! {
+                        pub fn #setter_name(mut self, value: #inner_ty) -> Self {
+                            self.#field = #wrapped;
+                            #touch
+
+                            self
+                        }
+                    });
+                }
+            }
+
+            if column.is_array() {
+                let push_name = format_ident!("push_{}", field);
+                let element_ty = column.element_kind.inner();
+                let values = derive_take(kind, quote::quote!(self.#field));
+                let wrapped = derive_wrap(kind, quote::quote!(values), nulls);
+
+                all_setters.push(quote::quote! {
+                    pub fn #push_name<Value: Into<#element_ty>>(mut self, value: Value) -> Self {
+                        let mut values = #values.unwrap_or_default();
+                        values.push(value.into());
+
+                        self.#field = #wrapped;
+                        #touch
+
+                        self
+                    }
+                });
+            }
+
+            if field == "id" {
+                all_setters.push(derive_id_setter(&table.attrs.id, kind, nulls)?);
+            }
+        }
+
+        // All clones
+        let clone_name = format_ident!("clone_{}", field);
+        let is_string = matches!(kind, FieldKind::Plain(_)) && column.inner_ty_str == "String";
+
+        match is_string {
+            _ if attrs.no_setter.is_set() => {},
+            true => all_clones.push(quote::quote! {
+                pub fn #clone_name(mut self, value: #ty) -> Self {
+                    self.#field = value;
+                    #touch
+
+                    self
+                }
+            }),
+            false => all_clones.push(quote::quote! {
+                pub fn #clone_name(mut self, value: &#ty) -> Self {
+                    self.#field = value.clone();
+                    #touch
+
+                    self
+                }
+            })
+        }
+
+        // All Null ⟶ Undefined
+        let clear_name = format_ident!("clear_{}", field);
+        if kind.is_null() {
             all_clears.push(quote::quote! {
                 pub fn #clear_name(mut self) -> Self {
-                    self.#field = nulls::undefined();
+                    self.#field = #nulls::undefined();
+
+                    self
+                }
+            });
+        }
+    }
+
+    Ok(quote::quote! {
+        #(#all_props)*
+
+        #(#all_setters)*
+
+        #(#all_setter_opts)*
+
+        #(#all_clones)*
+
+        #(#all_clears)*
+    })
+}
+
+// Create Sub Parsers reading the columns selected under each alias
+fn derive_alias_parsers(table: &Table) -> TS2 {
+    let sub_parsers = derive_alias_columns(table).into_iter().map(|(alias, columns)| {
+        let k = format_ident!("parse_{}", alias);
+        let fields = columns.iter().map(|c| &c.field);
+        let decoders = columns.iter().map(|c| c.decoder(&format!("{}_{}", alias, c.plain), &table.nulls));
+
+        quote::quote! {
+            pub fn #k(row: &sqlx::postgres::PgRow) -> Self {
+                 use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#fields = #decoders;
+                )*
+
+                data
+            }
+        }
+    });
+
+    quote::quote! {
+        #(#sub_parsers)*
+    }
+}
+
+// Create Relations
+fn derive_relations(table: &Table) -> deluxe::Result<TS2> {
+    let Table { ast, table_name, nulls, .. } = table;
+    let mut relations = Vec::<TS2>::new();
+
+    for (field, _ty, is_attributed, attrs) in
+        derive_utils::derive_try_all_fields::<&str, RelationAttrs>(ast, "relation")?
+    {
+        if !is_attributed {
+            continue;
+        }
+
+        let join_name = format_ident!("join_{}", field);
+        let select_name = format_ident!("select_with_{}", field);
+        let parse_name = format_ident!("parse_with_{}", field);
+
+        if let Some(related) = attrs.belongs_to {
+            let related_ty = related.parse::<Type>()?;
+            let fk = attrs.fk
+                .map(|s| s.value())
+                .unwrap_or(format!("{}_id", derive_utils::derive_snake_case(related.value())));
+
+            relations.push(quote::quote! {
+                pub fn #join_name() -> String {
+                    format!("LEFT JOIN {0} ON {0}.id = {1}.{2}", <#related_ty>::TABLE, #table_name, #fk)
+                }
+
+                pub fn #select_name() -> String {
+                    format!("{}, {}", Self::ALIAS_ALL, <#related_ty>::ALIAS_ALL)
+                }
+
+                pub fn #parse_name(row: &sqlx::postgres::PgRow) -> Self {
+                    let mut data = Self::parse(row);
+                    let related = <#related_ty>::parse(row);
+
+                    data.#field = match related.is_empty() {
+                        true => #nulls::undefined(),
+                        false => #nulls::new(related)
+                    };
+
+                    data
+                }
+            });
+        } else if let Some(related) = attrs.has_many {
+            let related_ty = related.parse::<Type>()?;
+            let fk = attrs.fk
+                .map(|s| s.value())
+                .unwrap_or(format!("{}_id", table_name));
+            let parent_key = format!("{}_id", table_name);
+
+            relations.push(quote::quote! {
+                pub fn #join_name() -> String {
+                    format!("LEFT JOIN {0} ON {0}.{1} = {2}.id", <#related_ty>::TABLE, #fk, #table_name)
+                }
+
+                pub fn #select_name() -> String {
+                    format!("{}, {}", Self::ALIAS_ALL, <#related_ty>::ALIAS_ALL)
+                }
+
+                pub fn #parse_name(rows: &[sqlx::postgres::PgRow]) -> Vec<Self> {
+                    use sqlx::Row;
+
+                    let mut indexes = std::collections::HashMap::<String, usize>::new();
+                    let mut data = Vec::<Self>::new();
+
+                    for row in rows {
+                        let Ok(key) = row.try_get::<String, &str>(#parent_key) else {
+                            continue;
+                        };
+
+                        let index = *indexes.entry(key).or_insert_with(|| {
+                            data.push(Self::parse(row));
+                            data.len() - 1
+                        });
+
+                        let related = <#related_ty>::parse(row);
+                        if !related.is_empty() {
+                            let mut children = data[index].#field.clone().take().unwrap_or_default();
+                            children.push(related);
+
+                            data[index].#field = #nulls::new(children);
+                        }
+                    }
+
+                    data
+                }
+            });
+        }
+    }
+
+    Ok(quote::quote! {
+        #(#relations)*
+    })
+}
+
+// Create full-text searches over the search vector columns
+fn derive_searches(table: &Table) -> TS2 {
+    let Table { table_name, responder, commenter, .. } = table;
+
+    let searches = table.attributed()
+        .filter(|c| c.attrs.tsvector.is_set())
+        .enumerate()
+        .map(|(index, column)| {
+            let search_name = match index {
+                0 => format_ident!("search"),
+                _ => format_ident!("search_{}", column.field)
+            };
+
+            let language = column.attrs.language
+                .as_ref()
+                .map(|l| l.value())
+                .unwrap_or("english".to_string());
+
+            // Tenant scoped searches take the tenant as an explicit parameter
+            let (tenant_param, tenant_into, tenant_sql, tenant_bind) = match table.tenant() {
+                Some(tenant) => {
+                    let tenant_ty = &tenant.inner_ty;
+                    let value = derive_encoder(quote::quote!(tenant.clone()), tenant.codec, false);
+
+                    (
+                        quote::quote!(, tenant: impl Into<#tenant_ty>),
+                        quote::quote!(let tenant = Into::<#tenant_ty>::into(tenant);),
+                        format!(" AND {}.{} = $2", table_name, tenant.plain),
+                        quote::quote!(.bind(#value))
+                    )
+                },
+                None => (quote::quote!(), quote::quote!(), String::new(), quote::quote!())
+            };
+
+            let sql = format!(
+                "SELECT {{}}, ts_rank({0}.{1}, plainto_tsquery('{2}', $1)) AS {0}_rank FROM {0} \
+                WHERE {0}.{1} @@ plainto_tsquery('{2}', $1){3} ORDER BY {0}_rank DESC",
+                table_name, column.plain, language, tenant_sql
+            );
+
+            let rows = derive_traced(table.execution, quote::quote!(Self), "select", quote::quote!(&sql), quote::quote! {
+                sqlx::query(&sql)
+                    .bind(term.to_string())
+                    #tenant_bind
+                    .fetch_all(database::writer())
+            });
+
+            quote::quote! {
+                pub async fn #search_name<Term>(term: Term #tenant_param) -> #responder::Result<Vec<Self>>
+                where
+                    Term: ToString
+                {
+                    #tenant_into
+                    let sql = format!(#sql, Self::ALIAS_ALL);
+                    #commenter
+                    let rows = #rows.map_err(#responder::query)?;
+
+                    Ok(rows.iter().map(Self::parse).collect())
+                }
+            }
+        });
+
+    quote::quote! {
+        #(#searches)*
+    }
+}
+
+// Create validators of the values allowed in enum columns
+fn derive_enum_validators(table: &Table) -> TS2 {
+    let responder = &table.responder;
+
+    let validators = table.attributed()
+        .filter(|c| c.codec == Codec::Enum)
+        .map(|column| {
+            let inner_ty = &column.inner_ty;
+            let validator = format_ident!("validate_{}", column.field);
+            let invalid = format!("Invalid {} value", column.field);
+
+            quote::quote! {
+                pub fn #validator<Value: ToString>(value: Value) -> #responder::Result<()> {
+                    let value = value.to_string();
+
+                    match <#inner_ty>::VALUES.iter().any(|v| v.eq_ignore_ascii_case(&value)) {
+                        true => Ok(()),
+                        false => Err(#responder::to(#invalid))
+                    }
+                }
+            }
+        });
+
+    quote::quote! {
+        #(#validators)*
+    }
+}
+
+// Create Cache implementation
+fn derive_cache(table: &Table, cache: &LitStr) -> deluxe::Result<TS2> {
+    let Table { ast, node, table_name, responder, commenter, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let Some(key) = table.attributed().find(|c| c.field == cache.value()) else {
+        return Err(syn::Error::new(
+            cache.span(),
+            format!("cache key `{}` is not a column of {}", cache.value(), node)
+        ));
+    };
+
+    // Statics cannot depend on generic parameters
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(cache.span(), format!("cache is not supported on generic model {}", node)));
+    }
+
+    // The cache holds the whole table, which would hand every tenant's rows to any caller
+    if table.tenant.is_some() {
+        return Err(syn::Error::new(cache.span(), format!("cache is not supported on tenant scoped model {}", node)));
+    }
+
+    let key_field = &key.field;
+    let key_ty = &key.inner_ty;
+    let key_value = derive_take(&key.kind, quote::quote!(row.#key_field));
+    let cache_sql = format!("SELECT {} FROM {}", table.attributed().map(|c| c.aliased.as_str()).collect::<Vec<&str>>().join(", "), table_name);
+    let cache_rows = derive_traced(table.execution, quote::quote!(Self), "select", quote::quote!(&sql), quote::quote! {
+        sqlx::query(&sql).fetch_all(pool)
+    });
+
+    Ok(quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            fn cache() -> &'static std::sync::RwLock<Option<std::sync::Arc<std::collections::HashMap<#key_ty, Self>>>> {
+                static CACHE: std::sync::OnceLock<std::sync::RwLock<Option<std::sync::Arc<std::collections::HashMap<#key_ty, #node>>>>> = std::sync::OnceLock::new();
+
+                CACHE.get_or_init(|| std::sync::RwLock::new(None))
+            }
+
+            pub async fn load_all_cached(pool: &sqlx::PgPool) -> #responder::Result<std::sync::Arc<std::collections::HashMap<#key_ty, Self>>> {
+                let cached = Self::cache()
+                    .read()
+                    .ok()
+                    .and_then(|cache| cache.clone());
+
+                match cached {
+                    Some(cached) => Ok(cached),
+                    None => Self::refresh(pool).await
+                }
+            }
+
+            pub async fn find_cached(pool: &sqlx::PgPool, key: &#key_ty) -> #responder::Result<Option<Self>> {
+                Ok(Self::load_all_cached(pool).await?.get(key).cloned())
+            }
+
+            pub async fn refresh(pool: &sqlx::PgPool) -> #responder::Result<std::sync::Arc<std::collections::HashMap<#key_ty, Self>>> {
+                let sql = #cache_sql.to_string();
+                #commenter
+                let rows = #cache_rows.map_err(#responder::query)?;
+
+                let records = std::sync::Arc::new(rows
+                    .iter()
+                    .map(Self::parse)
+                    .filter_map(|row| #key_value.map(|key| (key, row)))
+                    .collect::<std::collections::HashMap<#key_ty, Self>>());
+
+                if let Ok(mut cache) = Self::cache().write() {
+                    *cache = Some(records.clone());
+                }
+
+                Ok(records)
+            }
+
+            pub fn invalidate_cache() {
+                if let Ok(mut cache) = Self::cache().write() {
+                    *cache = None;
+                }
+            }
+        }
+    })
+}
+
+// Create Notify implementation
+fn derive_notify(table: &Table) -> deluxe::Result<TS2> {
+    let Table { node, table_name, responder, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let Some(id) = table.id() else {
+        return Err(syn::Error::new(node.span(), format!("notify requires an id column on {}", node)));
+    };
+
+    let id_ty = &id.inner_ty;
+    let malformed = format!("Malformed {} notification payload", table_name);
+    let notified = derive_traced(table.execution, quote::quote!(Self), "notify", quote::quote!(sql), quote::quote! {
+        sqlx::query(sql)
+            .bind(Self::TABLE)
+            .bind(payload.as_str())
+            .execute(database::writer())
+    });
+
+    Ok(quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub async fn notify_changed(&self) -> #responder::Result<()> {
+                let payload = serde_json::to_string(&self.id()).unwrap_or_default();
+                let sql = "SELECT pg_notify($1, $2)";
+
+                #notified.map_err(#responder::query)?;
+
+                Ok(())
+            }
+
+            pub async fn listen(pool: &sqlx::PgPool) -> #responder::Result<sqlx::postgres::PgListener> {
+                let mut listener = sqlx::postgres::PgListener::connect_with(pool)
+                    .await
+                    .map_err(#responder::query)?;
+
+                listener.listen(Self::TABLE)
+                    .await
+                    .map_err(#responder::query)?;
+
+                Ok(listener)
+            }
+
+            // Notifications are received one at a time, leaving streams to whichever crate the caller uses
+            pub async fn next_changed(listener: &mut sqlx::postgres::PgListener) -> #responder::Result<#id_ty> {
+                let notification = listener.recv()
+                    .await
+                    .map_err(#responder::query)?;
+
+                serde_json::from_str::<#id_ty>(notification.payload())
+                    .map_err(|_| #responder::to(#malformed))
+            }
+        }
+    })
+}
+
+// Create Retention implementation
+fn derive_retention(table: &Table, retention: &RetentionAttrs) -> deluxe::Result<TS2> {
+    let Table { node, table_name, responder, commenter, impl_generics, ty_generics, where_clause, .. } = table;
+
+    for column in std::iter::once(&retention.column).chain(&retention.soft_delete) {
+        if !table.attributed().any(|c| c.selected == column.value()) {
+            return Err(syn::Error::new(
+                column.span(),
+                format!("retention column `{}` is not a column of {}", column.value(), node)
+            ));
+        }
+    }
+
+    let days = retention.days.base10_parse::<i32>()?;
+
+    // Tenant scoped purges only reach the rows of the given tenant
+    let (tenant_param, tenant_arg, tenant_into, tenant_sql, tenant_bind) = match table.tenant() {
+        Some(tenant) => {
+            let tenant_ty = &tenant.inner_ty;
+            let value = derive_encoder(quote::quote!(tenant.clone()), tenant.codec, false);
+
+            (
+                quote::quote!(tenant: impl Into<#tenant_ty>,),
+                quote::quote!(tenant,),
+                quote::quote!(let tenant = Into::<#tenant_ty>::into(tenant);),
+                format!(" AND {} = $3", tenant.plain),
+                quote::quote!(.bind(#value))
+            )
+        },
+        None => (quote::quote!(), quote::quote!(), quote::quote!(), String::new(), quote::quote!())
+    };
+
+    let expired = format!(
+        "SELECT ctid FROM {} WHERE {} < now() - make_interval(days => $1){}",
+        table_name, retention.column.value(), tenant_sql
+    );
+
+    // Soft deletes stamp rows once, so stamped rows are left out of later batches
+    let (kind, purge_sql) = match &retention.soft_delete {
+        Some(column) => ("update", format!(
+            "UPDATE {0} SET {1} = now() WHERE ctid IN ({2} AND {1} IS NULL LIMIT $2)",
+            table_name, column.value(), expired
+        )),
+        None => ("delete", format!(
+            "DELETE FROM {} WHERE ctid IN ({} LIMIT $2)",
+            table_name, expired
+        ))
+    };
+
+    let purged = derive_traced(table.execution, quote::quote!(Self), kind, quote::quote!(&sql), quote::quote! {
+        sqlx::query(&sql)
+            .bind(Self::RETENTION_DAYS)
+            .bind(batch_size)
+            #tenant_bind
+            .execute(pool)
+    });
+
+    Ok(quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub const RETENTION_DAYS: i32 = #days;
+
+            pub async fn purge_expired(pool: &sqlx::PgPool, #tenant_param batch_size: i64) -> #responder::Result<u64> {
+                Self::purge_expired_with(pool, #tenant_arg batch_size, |_| {}).await
+            }
+
+            pub async fn purge_expired_with<Progress>(pool: &sqlx::PgPool, #tenant_param batch_size: i64, mut progress: Progress) -> #responder::Result<u64>
+            where
+                Progress: FnMut(u64)
+            {
+                #tenant_into
+                let batch_size = batch_size.max(1);
+                let sql = #purge_sql.to_string();
+                #commenter
+
+                let mut total = 0;
+
+                loop {
+                    let affected = #purged.map_err(#responder::query)?.rows_affected();
+
+                    total += affected;
+                    progress(total);
+
+                    if affected < batch_size as u64 {
+                        return Ok(total);
+                    }
+                }
+            }
+        }
+    })
+}
+
+// Create Advisory lock implementation
+fn derive_lock(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let unidentified = format!("Cannot lock a {} record without an id", table_name);
+    let unlocked = Execution { guarded: false, ..table.execution };
+    let locked = derive_traced(unlocked, quote::quote!(Self), "lock", quote::quote!(sql), quote::quote! {
+        sqlx::query(sql).bind(key).execute(&mut **transaction)
+    });
+    let try_locked = derive_traced(unlocked, quote::quote!(Self), "lock", quote::quote!(sql), quote::quote! {
+        sqlx::query_scalar::<_, bool>(sql).bind(key).fetch_one(&mut **transaction)
+    });
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            // Locks are keyed on the table and id, hashed into postgres' advisory lock space. They are
+            // released when the transaction ends, so they only take a transaction and never a bare pool
+            fn lock_key(&self) -> #responder::Result<String> {
+                match self.id.is_some() {
+                    true => Ok(format!("{}:{}", Self::TABLE, serde_json::to_string(&self.id).unwrap_or_default())),
+                    false => Err(#responder::to(#unidentified))
+                }
+            }
+
+            pub async fn lock(&self, transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>) -> #responder::Result<()> {
+                let key = self.lock_key()?;
+                let sql = "SELECT pg_advisory_xact_lock(hashtext($1))";
+
+                #locked.map_err(#responder::query)?;
+
+                Ok(())
+            }
+
+            pub async fn try_lock(&self, transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>) -> #responder::Result<bool> {
+                let key = self.lock_key()?;
+                let sql = "SELECT pg_try_advisory_xact_lock(hashtext($1))";
+
+                #try_locked.map_err(#responder::query)
+            }
+        }
+    }
+}
+
+// Create Health-check implementation, opt-in as its timeout runs on tokio
+fn derive_healthcheck(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let healthcheck = format!("SELECT 1 FROM {} LIMIT 1", table_name);
+    let unhealthy = format!("Health check on {} table timed out", table_name);
+    let probe = derive_traced(table.execution, quote::quote!(Self), "select", quote::quote!(#healthcheck), quote::quote! {
+        sqlx::query(#healthcheck).fetch_optional(pool)
+    });
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub const HEALTHCHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+            pub async fn healthcheck(pool: &sqlx::PgPool) -> #responder::Result<std::time::Duration> {
+                let started = std::time::Instant::now();
+                let query = async { #probe };
+
+                match tokio::time::timeout(Self::HEALTHCHECK_TIMEOUT, query).await {
+                    Ok(result) => result.map(|_| started.elapsed()).map_err(#responder::query),
+                    Err(_) => Err(#responder::to(#unhealthy))
+                }
+            }
+        }
+    }
+}
+
+// Create Base conversion, also failing to compile when the inherited columns drift
+fn derive_extends(table: &Table, extends: &LitStr) -> deluxe::Result<TS2> {
+    let Table { ast, node, .. } = table;
+
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            extends.span(),
+            format!("extends is not supported on generic model {}", node)
+        ));
+    }
+
+    let path = extends.parse::<syn::Path>()?;
+    let base = derive_base_macro(&path);
+
+    Ok(quote::quote!{
+        #base!(@from #path, #node);
+    })
+}
+
+// Create Projection implementation
+fn derive_projection(table: &Table, projection: &ProjectionAttrs) -> deluxe::Result<TS2> {
+    let Table { ast, node, nulls, .. } = table;
+
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            projection.name.span(),
+            format!("projection is not supported on generic model {}", node)
+        ));
+    }
+
+    let name = projection.name.parse::<Ident>()?;
+    let mut fields = Vec::<&Ident>::new();
+    let mut field_attrs = Vec::<Vec<Attribute>>::new();
+    let mut tys = Vec::<&Type>::new();
+    let mut decoders = Vec::<TS2>::new();
+    let mut plain_decoders = Vec::<TS2>::new();
+    let mut aliased = Vec::<&str>::new();
+    let mut plain = Vec::<&str>::new();
+
+    for field in projection.fields.value().split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        // Fields keep their type and serde attributes so the projection serializes like the model
+        let column = table.attributed().find(|c| c.field == field);
+        let source = match &ast.data {
+            syn::Data::Struct(data) => data.fields.iter().find(|f| f.ident.as_ref().is_some_and(|i| *i == field)),
+            _ => None,
+        };
+
+        let (Some(column), Some(source)) = (column, source) else {
+            return Err(syn::Error::new(
+                projection.fields.span(),
+                format!("projection field `{}` is not a column of {}", field, node)
+            ));
+        };
+
+        fields.push(&column.field);
+        field_attrs.push(source.attrs.iter().filter(|a| a.path().is_ident("serde")).cloned().collect());
+        tys.push(&source.ty);
+        decoders.push(column.decoder(&column.renamed, nulls));
+        plain_decoders.push(column.decoder(&column.plain, nulls));
+        aliased.push(&column.aliased);
+        plain.push(&column.selected);
+    }
+
+    let vis = &ast.vis;
+    let serde_attrs = ast.attrs.iter().filter(|a| a.path().is_ident("serde"));
+    let doc = format!("Projection of {} over `{}`", node, projection.fields.value());
+    let aliased_str = aliased.join(", ");
+    let plain_str = plain.join(", ");
+
+    Ok(quote::quote!{
+        #[doc = #doc]
+        #[derive(Clone, Default, PartialEq, serde::Serialize)]
+        #(#serde_attrs)*
+        #vis struct #name {
+            #(
+                #(#field_attrs)*
+                pub #fields: #tys,
+            )*
+        }
+
+        impl #name {
+            pub const ALIAS_ALL: &'static str = #aliased_str;
+            pub const PLAIN_ALL: &'static str = #plain_str;
+
+            pub fn parse(row: &sqlx::postgres::PgRow) -> Self {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#fields = #decoders;
+                )*
+
+                data
+            }
+        }
+
+        impl From<#node> for #name {
+            fn from(value: #node) -> Self {
+                Self {
+                    #(
+                        #fields: value.#fields,
+                    )*
+                }
+            }
+        }
+
+        impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for #name {
+            fn from_row(row: &'r sqlx::postgres::PgRow) -> sqlx::Result<Self> {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(
+                    data.#fields = #plain_decoders;
+                )*
+
+                Ok(data)
+            }
+        }
+    })
+}
+
+// Create Statistics implementation
+fn derive_stats(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, nulls, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let missing_stats = format!("No statistics found for {} table", table_name);
+    let stats_sql = "SELECT n_live_tup, n_dead_tup, last_vacuum, last_autovacuum, last_analyze, last_autoanalyze FROM pg_stat_user_tables WHERE relname = $1";
+    let stats_row = derive_traced(table.execution, quote::quote!(Self), "select", quote::quote!(#stats_sql), quote::quote! {
+        sqlx::query(#stats_sql)
+            .bind(Self::TABLE)
+            .fetch_optional(pool)
+    });
+
+    quote::quote!{
+        pub mod stats {
+            #[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+            pub struct Stats {
+                pub row_estimate: i64,
+                pub dead_tuples: i64,
+                pub last_vacuum: #nulls::Null<chrono::DateTime<chrono::Utc>>,
+                pub last_autovacuum: #nulls::Null<chrono::DateTime<chrono::Utc>>,
+                pub last_analyze: #nulls::Null<chrono::DateTime<chrono::Utc>>,
+                pub last_autoanalyze: #nulls::Null<chrono::DateTime<chrono::Utc>>,
+            }
+        }
+
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub async fn stats(pool: &sqlx::PgPool) -> #responder::Result<stats::Stats> {
+                use sqlx::Row;
+
+                let timestamp = |row: &sqlx::postgres::PgRow, column: &str| {
+                    #nulls::Null::from(row.try_get::<Option<chrono::DateTime<chrono::Utc>>, &str>(column).ok().flatten())
+                };
+
+                let row = #stats_row
+                    .map_err(#responder::query)?
+                    .ok_or_else(|| #responder::to(#missing_stats))?;
+
+                Ok(stats::Stats {
+                    row_estimate: row.try_get("n_live_tup").map_err(#responder::query)?,
+                    dead_tuples: row.try_get("n_dead_tup").map_err(#responder::query)?,
+                    last_vacuum: timestamp(&row, "last_vacuum"),
+                    last_autovacuum: timestamp(&row, "last_autovacuum"),
+                    last_analyze: timestamp(&row, "last_analyze"),
+                    last_autoanalyze: timestamp(&row, "last_autoanalyze"),
+                })
+            }
+        }
+    }
+}
+
+// Create sqlcommenter implementation
+fn derive_sql_comment(table: &Table) -> TS2 {
+    let Table { node, impl_generics, ty_generics, where_clause, .. } = table;
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub fn sql_comment() -> String {
+                use opentelemetry::trace::TraceContextExt;
+
+                let context = opentelemetry::Context::current();
+                let span = context.span();
+                let span_context = span.span_context();
+
+                if !span_context.is_valid() {
+                    return String::new();
+                }
+
+                format!(
+                    " /*traceparent='00-{}-{}-{:02x}'*/",
+                    span_context.trace_id(),
+                    span_context.span_id(),
+                    span_context.trace_flags().to_u8()
+                )
+            }
+        }
+    }
+}
+
+// Create tracing and metrics implementation
+fn derive_trace_query(table: &Table) -> TS2 {
+    let Table { node, table_name, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let (allow, span, awaited, record) = match cfg!(feature = "tracing") {
+        true => (
+            quote::quote! {},
+            quote::quote! {
+                use tracing::Instrument;
+
+                let span = tracing::debug_span!(
+                    "db.query",
+                    db.table = #table_name,
+                    db.statement = kind,
+                    db.duration_ms = tracing::field::Empty
+                );
+
+                // Binds are never part of the statement, placeholders are logged in their place
+                tracing::debug!(parent: &span, sql = sql.trim(), "executing {} on {}", kind, #table_name);
+            },
+            quote::quote! { query.instrument(span.clone()).await },
+            quote::quote! { span.record("db.duration_ms", elapsed.as_millis() as u64); }
+        ),
+        false => (quote::quote! { #[allow(unused_variables)] }, quote::quote! {}, quote::quote! { query.await }, quote::quote! {})
+    };
+
+    let observe = match &table.metrics {
+        Some(metrics) => quote::quote! {
+            <#metrics as derives::QueryObserver>::observe(#table_name, kind, elapsed);
+        },
+        None => quote::quote! {}
+    };
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            #allow
+            pub async fn trace_query<Output>(kind: &'static str, sql: &str, query: impl std::future::Future<Output = Output>) -> Output {
+                #span
+
+                let started = std::time::Instant::now();
+                let output = #awaited;
+                let elapsed = started.elapsed();
+
+                #record
+                #observe
+
+                output
+            }
+        }
+    }
+}
+
+// Create statement policy implementation
+fn derive_guard_query(table: &Table) -> TS2 {
+    let Table { node, table_name, retries, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let timed_out = format!("{{}} on {} timed out", table_name);
+    let (timeout, timed) = match table.timeout_ms {
+        Some(timeout_ms) => (
+            quote::quote!(Some(std::time::Duration::from_millis(#timeout_ms))),
+            quote::quote! {
+                match tokio::time::timeout(std::time::Duration::from_millis(#timeout_ms), query).await {
+                    Ok(result) => (result, false),
+                    Err(_) => (Err(sqlx::Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!(#timed_out, kind)
+                    ))), true),
+                }
+            }
+        ),
+        // Only timeouts run on tokio, retries alone need no runtime
+        None => (quote::quote!(None), quote::quote!((query.await, false))),
+    };
+    let (allow, attempt) = match table.execution.instrumented {
+        true => (quote::quote! {}, quote::quote!(Self::trace_query(kind, sql, call()))),
+        false => (quote::quote! { #[allow(unused_variables)] }, quote::quote!(call())),
+    };
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub const QUERY_TIMEOUT: Option<std::time::Duration> = #timeout;
+            pub const QUERY_RETRIES: u32 = #retries;
+
+            // Serialization failures and deadlocks roll the statement back, and a pool timeout never sent it,
+            // so they are worth another attempt whatever the statement does
+            pub fn is_transient(e: &sqlx::Error) -> bool {
+                match e {
+                    sqlx::Error::Database(e) => matches!(e.code().as_deref(), Some("40001" | "40P01")),
+                    sqlx::Error::PoolTimedOut => true,
+                    _ => false,
+                }
+            }
+
+            #allow
+            pub async fn guard_query<Output, Query>(kind: &'static str, sql: &str, mut call: impl FnMut() -> Query) -> sqlx::Result<Output>
+            where
+                Query: std::future::Future<Output = sqlx::Result<Output>>
+            {
+                let mut attempt = 0;
+
+                loop {
+                    let query = #attempt;
+                    let (result, timed_out) = #timed;
+
+                    // Writes that timed out may have committed on the server, only reads are retried then
+                    let retryable = match &result {
+                        Err(e) => Self::is_transient(e) || (timed_out && kind == "select"),
+                        Ok(_) => false,
+                    };
+
+                    match result {
+                        Err(_) if retryable && attempt < Self::QUERY_RETRIES => attempt += 1,
+                        result => return result,
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Create Insert implementation
+fn derive_insert(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, commenter, db_error, returned, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let all_insert_undefined = table.inserted().map(|c| {
+        let field = &c.field;
+
+        c.kind.undefined(quote::quote!(self.#field))
+    }).collect::<Vec<TS2>>();
+    let all_insert_columns = table.inserted().map(|c| c.plain.as_str()).collect::<Vec<&str>>();
+    let all_insert_binds = table.inserted().map(|c| c.encoder(quote::quote!(self))).collect::<Vec<TS2>>();
+    let all_insert_timestamps = table.insert_timestamps();
+
+    // Create the statement of records with every column set, built once so identical writes share one string
+    let insert_all = {
+        let placeholders = derive_utils::derive_number_placeholders(&vec!["${}"; all_insert_columns.len()], 0);
+        let values = placeholders.into_iter().chain(all_insert_timestamps.iter().map(|_| "now()".to_string()));
+        let columns = all_insert_columns.iter().chain(&all_insert_timestamps).copied();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
+            table_name, columns.collect::<Vec<&str>>().join(", "), values.collect::<Vec<String>>().join(", "), table.returning
+        )
+    };
+
+    let insert_binds = all_insert_columns.len();
+    let insert_all_path = match all_insert_columns.is_empty() {
+        true => quote::quote! {},
+        false => quote::quote! {
+            if true #(&& !#all_insert_undefined)* {
+                let sql = Self::INSERT_ALL_SQL.to_string();
+                #commenter
+
+                return (sql, #insert_binds);
+            }
+        }
+    };
+
+    // Search vectors and computed columns are maintained by the database and cannot be copied into
+    let all_copy_columns_str = table.attributed()
+        .filter(|c| c.is_writable())
+        .map(|c| c.plain.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let all_copy_values = table.attributed()
+        .filter(|c| c.is_writable())
+        .map(|c| derive_copy_value(&c.field, &c.kind, c.codec, c.inner_ty_str == "Vec<u8>"));
+
+    let inserted = derive_traced(table.execution, quote::quote!(Self), "insert", quote::quote!(&sql), quote::quote!(query().fetch_one(database::writer())));
+    let copied = derive_traced(Execution { guarded: false, ..table.execution }, quote::quote!(Self), "copy", quote::quote!(&sql), quote::quote!(copy));
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub async fn insert(&self) -> #responder::Result<Self> {
+                self.try_insert().await.map_err(Into::into)
+            }
+
+            pub async fn try_insert(&self) -> Result<Self, #db_error> {
+                let (sql, _) = self.insert_sql();
+
+                // Statements are rebuilt from the record on every attempt
+                let query = || {
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !#all_insert_undefined {
+                            query = query.bind(#all_insert_binds);
+                        }
+                    )*
+
+                    query
+                };
+
+                parsers::try_result(#inserted)#returned
+            }
+
+            pub const INSERT_ALL_SQL: &'static str = #insert_all;
+
+            pub fn insert_sql(&self) -> (String, usize) {
+                #insert_all_path
+
+                let mut index = 0;
+                let mut columns = Vec::<String>::new();
+                let mut values = Vec::<String>::new();
+
+                #(
+                    if !#all_insert_undefined {
+                        index += 1;
+                        columns.push(#all_insert_columns.to_string());
+                        values.push(format!("${}", index));
+                    }
+                )*
+
+                #(
+                    columns.push(#all_insert_timestamps.to_string());
+                    values.push("now()".to_string());
+                )*
+
+                let sql = format!(
+                    "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
+                    #table_name, columns.join(", "), values.join(", "), Self::RETURNING
+                );
+                #commenter
+
+                (sql, index)
+            }
+
+            pub const COPY_CHUNK_BYTES: usize = 1 << 20;
+
+            pub async fn copy_in(rows: &[Self]) -> #responder::Result<u64> {
+                use sqlx::postgres::PgPoolCopyExt;
+
+                if rows.is_empty() {
+                    return Ok(0);
+                }
+
+                let sql = format!("COPY {} ({}) FROM STDIN WITH (FORMAT csv)", #table_name, #all_copy_columns_str);
+                #commenter
+
+                // Streamed as a single future so the whole transfer is measured
+                let copy = async {
+                    let mut copy = database::writer()
+                        .copy_in_raw(&sql)
+                        .await
+                        .map_err(#responder::query)?;
+
+                    let mut buffer = String::new();
+
+                    for row in rows {
+                        let record = row.clone().build();
+                        let values: Vec<Option<String>> = vec![#(#all_copy_values),*];
+
+                        buffer.push_str(&derives::copy::record(values));
+
+                        if buffer.len() >= Self::COPY_CHUNK_BYTES {
+                            if let Err(e) = copy.send(std::mem::take(&mut buffer).into_bytes()).await {
+                                let _ = copy.abort(e.to_string()).await;
+
+                                return Err(#responder::query(e));
+                            }
+                        }
+                    }
+
+                    if let Err(e) = copy.send(buffer.into_bytes()).await {
+                        let _ = copy.abort(e.to_string()).await;
+
+                        return Err(#responder::query(e));
+                    }
+
+                    copy.finish().await.map_err(#responder::query)
+                };
+
+                #copied
+            }
+        }
+    }
+}
+
+// Create Update implementation
+fn derive_update(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, commenter, db_error, returned, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let all_update_undefined = table.updated().map(|c| table.untouched(c, quote::quote!(self))).collect::<Vec<TS2>>();
+    let all_update_columns = table.updated().map(|c| format!("{} = ${{}}", c.plain)).collect::<Vec<String>>();
+    let all_update_binds = table.updated().map(|c| c.encoder(quote::quote!(self))).collect::<Vec<TS2>>();
+    let all_update_timestamps = table.update_timestamps();
+
+    // Create statements of records with every column set, built once so identical writes share one string
+    let (update_all, update_all_silent, update_all_binds) = {
+        let mut updates = derive_utils::derive_number_placeholders(&all_update_columns, 0);
+        let mut conditions = vec!["id = ${}".to_string()];
+
+        updates.extend(all_update_timestamps.iter().cloned());
+
+        if let Some(tenant) = table.tenant() {
+            conditions.push(format!("{} = ${{}}", tenant.plain));
+        }
+
+        if let Some(version) = table.version() {
+            updates.push(format!("{0} = {0} + 1", version.plain));
+            conditions.push(format!("{} = ${{}}", version.plain));
+        }
+
+        // Conditions are bound after every updated column
+        let index = all_update_columns.len() + conditions.len();
+        let conditions = derive_utils::derive_number_placeholders(&conditions, all_update_columns.len());
+
+        let silent = format!("UPDATE {} SET {} WHERE {}", table_name, updates.join(", "), conditions.join(" AND "));
+        (format!("{} RETURNING {}", silent, table.returning), silent, index)
+    };
+
+    let update_all_path = match all_update_columns.is_empty() {
+        true => quote::quote! {},
+        false => quote::quote! {
+            if true #(&& !#all_update_undefined)* {
+                let sql = match returning {
+                    true => Self::UPDATE_ALL_SQL.to_string(),
+                    false => Self::UPDATE_ALL_SILENT_SQL.to_string()
+                };
+                #commenter
+
+                return Some((sql, #update_all_binds));
+            }
+        }
+    };
+
+    // Create optimistic locking
+    let fetch_one = derive_traced(table.execution, quote::quote!(Self), "update", quote::quote!(&sql), quote::quote!(query().fetch_one(database::writer())));
+    let fetch_optional = derive_traced(table.execution, quote::quote!(Self), "update", quote::quote!(&sql), quote::quote!(query().fetch_optional(database::writer())));
+    let execute = derive_traced(table.execution, quote::quote!(Self), "update", quote::quote!(&sql), quote::quote!(query().execute(database::writer())));
+    let (version_set, version_condition, version_bind, version_debug, update_result, silent_result) = match table.version() {
+        Some(version) => {
+            let field = &version.field;
+            let set = format!("{0} = {0} + 1", version.plain);
+            let condition = format!("{} = ${{}}", version.plain);
+
+            (
+                quote::quote! {
+                    updates.push(#set.to_string());
+                },
+                quote::quote! {
+                    index += 1;
+                    conditions.push(format!(#condition, index));
+                },
+                quote::quote! {
+                    let query = query.bind(self.#field());
+                },
+                quote::quote! {
+                    index += 1;
+                    conditions.push(format!(#condition, index));
+                    values.push(literal(serde_json::to_value(self.#field()).unwrap_or_default()));
+                },
+                quote::quote! {
+                    match #fetch_optional? {
+                        Some(row) => parsers::try_result(Ok(row))#returned,
+                        None => Err(#db_error::Stale)
+                    }
+                },
+                quote::quote! {
+                    match #execute?.rows_affected() {
+                        0 => Err(#db_error::Stale),
+                        affected => Ok(affected)
+                    }
+                },
+            )
+        },
+        None => (
+            quote::quote! {},
+            quote::quote! {},
+            quote::quote! {},
+            quote::quote! {},
+            quote::quote! {
+                parsers::try_result(#fetch_one)#returned
+            },
+            quote::quote! {
+                Ok(#execute?.rows_affected())
+            },
+        )
+    };
+
+    // Create tenant scoping of updates
+    let (tenant_condition, tenant_bind, tenant_debug) = match table.tenant() {
+        Some(tenant) => {
+            let field = &tenant.field;
+            let condition = format!("{} = ${{}}", tenant.plain);
+            let bind = tenant.encoder(quote::quote!(self));
+
+            (
+                quote::quote! {
+                    index += 1;
+                    conditions.push(format!(#condition, index));
+                },
+                quote::quote! {
+                    let query = query.bind(#bind);
+                },
+                quote::quote! {
+                    index += 1;
+                    conditions.push(format!(#condition, index));
+                    values.push(literal(serde_json::to_value(self.#field()).unwrap_or_default()));
+                }
+            )
+        },
+        None => (quote::quote! {}, quote::quote! {}, quote::quote! {})
+    };
+
+    quote::quote!{
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub async fn update(&self) -> #responder::Result<Self> {
+                self.try_update().await.map_err(Into::into)
+            }
+
+            pub async fn try_update(&self) -> Result<Self, #db_error> {
+                let Some((sql, _)) = self.update_statement(true) else {
+                    return Err(#db_error::Unchanged);
+                };
 
-                    self
-                }
-            });
-        }
+                // Statements are rebuilt from the record on every attempt
+                let query = || {
+                    let mut query = sqlx::query(&sql);
 
-        // Check if is_attributed
-        if is_attributed {
-            // Create basic table names and aliases
-            let plain = derive_utils::derive_snake_case(field.clone().to_string());
-            let renamed = format!("{}_{}", table_name, plain);
-            let tabled = format!("{}.{}", table_name, plain);
-            let aliased = format!("{} AS {}", tabled, renamed);
+                    #(
+                        if !#all_update_undefined {
+                            query = query.bind(#all_update_binds);
+                        }
+                    )*
 
-            all_attributed_fields.push(field.clone());
-            all_attributed_inner_ty.push(inner_ty.clone());
-            all_attributed_renamed.push(renamed.clone());
+                    let query = query.bind(self.id());
 
-            all_const_names.push(format_ident!("{}", plain.to_uppercase()));
-            all_aliased.push(aliased);
-            all_plain.push(plain.clone());
-            all_renamed.push(renamed.clone());
-            all_tabled.push(tabled.clone());
+                    #tenant_bind
+                    #version_bind
 
-            for a in aliases.clone() {
-                let aliased_parser = format_ident!("parse_{}", a);
-                let aliased_renamed = format!("{}_{}", a, plain);
-                let sub_aliased = format!("{} AS {}", tabled, aliased_renamed);
+                    query
+                };
 
-                map_sub_parser.entry(aliased_parser.clone())
-                    .and_modify(|d| d.push((field.clone(), inner_ty.clone(), aliased_renamed.clone())))
-                    .or_insert(vec![(field.clone(), inner_ty.clone(), aliased_renamed.clone())]);
+                #update_result
+            }
 
-                map_sub_alias.entry(aliased_parser.clone())
-                    .and_modify(|d| d.push(sub_aliased.clone()))
-                    .or_insert(vec![sub_aliased]);
+            pub async fn update_silent(&self) -> #responder::Result<u64> {
+                self.try_update_silent().await.map_err(Into::into)
             }
-        }
-    }
 
-    // Use explicit string join with &str type
-    let all_aliased_str = all_aliased.join(", ");
-    let all_plain_str = all_plain.join(", ");
-    let all_renamed_str = all_renamed.join(", ");
-    let all_tabled_str = all_tabled.join(", ");
+            pub async fn try_update_silent(&self) -> Result<u64, #db_error> {
+                let Some((sql, _)) = self.update_statement(false) else {
+                    return Err(#db_error::Unchanged);
+                };
 
-    // Create Sub Alias
-    //____________________________________________________________
-    let mut sub_alias = Vec::<TS2>::new();  // Specify type explicitly
-    for (k, v) in map_sub_alias {
-        let all_alias_str = v.join(", ");
-        let module = format_ident!("{}", k.to_string().replace("parse_", ""));
+                // Statements are rebuilt from the record on every attempt
+                let query = || {
+                    let mut query = sqlx::query(&sql);
 
-        sub_alias.push(quote::quote!{
-            pub mod #module {
-                pub const ALL: &'static str = #all_alias_str;
+                    #(
+                        if !#all_update_undefined {
+                            query = query.bind(#all_update_binds);
+                        }
+                    )*
 
-                #(
-                    pub const #all_const_names: &'static str = #v;
-                )*
+                    let query = query.bind(self.id());
+
+                    #tenant_bind
+                    #version_bind
+
+                    query
+                };
+
+                #silent_result
             }
-        });
-    }
 
-    // Create Sub Parsers
-    //____________________________________________________________
-    let mut sub_parsers = Vec::<TS2>::new();  // Specify type explicitly
-    let mut sub_parser_mod = Vec::<TS2>::new();  // Specify type explicitly
-    for (k, v) in map_sub_parser {
-        let mut fields = Vec::<Ident>::new();  // Specify type explicitly
-        let mut inner_ty = Vec::<Type>::new();  // Specify type explicitly
-        let mut aliases = Vec::<String>::new();  // Specify type explicitly
+            pub fn update_sql(&self) -> (String, usize) {
+                self.update_statement(true).unwrap_or_default()
+            }
 
-        let module = format_ident!("{}", k.to_string().replace("parse_", ""));
+            pub const UPDATE_ALL_SQL: &'static str = #update_all;
+            pub const UPDATE_ALL_SILENT_SQL: &'static str = #update_all_silent;
 
-        for (f, it, ar) in v {
-            fields.push(f);
-            inner_ty.push(it);
-            aliases.push(ar);
-        }
+            fn update_statement(&self, returning: bool) -> Option<(String, usize)> {
+                #update_all_path
 
-        sub_parsers.push(quote::quote! {
-            pub fn #k(row: &sqlx::postgres::PgRow) -> Self {
-                 use sqlx::Row;
+                let mut index = 0;
+                let mut updates = Vec::<String>::new();  // Specify type explicitly
 
-                let mut data = Self::default();
+                #(
+                    if !#all_update_undefined {
+                        index += 1;
+                        updates.push(format!(#all_update_columns, index));
+                    }
+                )*
+
+                // Timestamps and versions only follow actual changes
+                if updates.is_empty() {
+                    return None;
+                }
 
                 #(
-                    data.#fields = nulls::Null::from(row.try_get::<#inner_ty, &str>(#aliases));
+                    updates.push(#all_update_timestamps.to_string());
                 )*
 
-                data
-            }
-        });
+                #version_set
 
-        sub_parser_mod.push(quote::quote!{
-            pub mod #module {
-                use nulls::Null;
-                use sqlx::{Result, Row, postgres::PgRow};
+                index += 1;
+                let mut conditions = vec![format!("id = ${}", index)];
 
-                use crate::#node;
+                #tenant_condition
+                #version_condition
 
-                pub fn parse(row: &PgRow) -> #node {
-                    #node::#k(row)
-                }
+                let sql = match returning {
+                    true => format!(
+                        "UPDATE {} SET {} WHERE {} RETURNING {}",
+                        #table_name, updates.join(", "), conditions.join(" AND "), Self::RETURNING
+                    ),
+                    false => format!("UPDATE {} SET {} WHERE {}", #table_name, updates.join(", "), conditions.join(" AND "))
+                };
+                #commenter
 
-                pub fn result(row: Result<sqlx::postgres::PgRow>) -> responder::Result<#node> {
-                    let result = row.map_err(responder::query)?;
-                    let row = parse(&result);
+                Some((sql, index))
+            }
 
-                    match !row.is_empty() {
-                        true => Ok(row),
-                        false => Err(responder::to(#error))
-                    }
-                }
+            pub fn debug_update_sql(&self) -> String {
+                let literal = |value: serde_json::Value| match value {
+                    serde_json::Value::Null => "NULL".to_string(),
+                    serde_json::Value::Bool(b) => b.to_string().to_uppercase(),
+                    serde_json::Value::Number(n) => n.to_string(),
+                    serde_json::Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+                    value => format!("'{}'", value.to_string().replace('\'', "''")),
+                };
 
-                pub fn relational(row: &PgRow) -> Null<#node> {
-                    let row = parse(row);
+                let mut index = 0;
+                let mut updates = Vec::<String>::new();  // Specify type explicitly
+                let mut values = Vec::<String>::new();
 
-                    match row.is_empty() {
-                        true => nulls::undefined(),
-                        false => nulls::new(row)
+                #(
+                    if !#all_update_undefined {
+                        index += 1;
+                        updates.push(format!(#all_update_columns, index));
+                        values.push(literal(serde_json::to_value(#all_update_binds).unwrap_or_default()));
                     }
-                }
-            }
-        });
-    }
+                )*
 
-    // Create Sub-module Implementations
-    //____________________________________________________________
-    token.extend(quote::quote!{
-        pub mod alias {
-            pub const ALL: &'static str = #all_aliased_str;
+                #(
+                    updates.push(#all_update_timestamps.to_string());
+                )*
 
-            #(
-                pub const #all_const_names: &'static str = #all_aliased;
-            )*
+                #version_set
 
+                index += 1;
+                let mut conditions = vec![format!("id = ${}", index)];
+                values.push(literal(serde_json::to_value(self.id()).unwrap_or_default()));
 
-            #(#sub_alias)*
-        }
+                #tenant_debug
+                #version_debug
 
-        pub mod plain {
-            pub const ALL: &'static str = #all_plain_str;
+                let sql = format!(
+                    "UPDATE {} SET {} WHERE {} RETURNING {}",
+                    #table_name, updates.join(", "), conditions.join(" AND "), Self::RETURNING
+                );
 
-            #(
-                pub const #all_const_names: &'static str = #all_plain;
-            )*
-        }
+                // Substitute placeholders in a single pass so bound values are never rescanned
+                let mut rendered = String::with_capacity(sql.len());
+                let mut chars = sql.chars().peekable();
 
-        pub mod renamed {
-            pub const ALL: &'static str = #all_renamed_str;
+                while let Some(c) = chars.next() {
+                    let mut digits = String::new();
+                    while c == '$' && chars.peek().is_some_and(|d| d.is_ascii_digit()) {
+                        digits.extend(chars.next());
+                    }
 
-            #(
-                pub const #all_const_names: &'static str = #all_renamed;
-            )*
+                    match digits.parse::<usize>().ok().and_then(|i| values.get(i.checked_sub(1)?)) {
+                        Some(value) => rendered.push_str(value),
+                        None => {
+                            rendered.push(c);
+                            rendered.push_str(&digits);
+                        }
+                    }
+                }
+
+                rendered
+            }
         }
+    }
+}
 
-        pub mod tabled {
-            pub const ALL: &'static str = #all_tabled_str;
+// Create Admin Routes over the record addressed by `id`
+fn derive_admin(table: &Table, id: &Column) -> TS2 {
+    let Table { node, table_name, responder, commenter, nulls, error, turbofish, generic_scope, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let id_ty = &id.inner_ty;
+    let scope = format!("/{}", table_name);
+    let record_id = derive_wrap(&id.kind, quote::quote!(id), nulls);
+
+    // Tenant scoped routes are nested under the tenant, which every handler filters or stamps with
+    let (tenant_ty, tenant_route, tenant_arg, tenant_new, tenant_unpack, tenant_stamp, tenant_sql, tenant_bind) = match table.tenant() {
+        Some(tenant) => {
+            let field = &tenant.field;
+            let tenant_ty = &tenant.inner_ty;
+            let value = derive_encoder(quote::quote!(tenant.clone()), tenant.codec, false);
+            let stamped = derive_wrap(&tenant.kind, quote::quote!(tenant), nulls);
+
+            (
+                quote::quote!(#tenant_ty,),
+                "/{tenant}",
+                quote::quote!(tenant: web::Path<#tenant_ty>),
+                quote::quote!(new(tenant)),
+                quote::quote!((tenant, id)),
+                quote::quote!(record.#field = #stamped;),
+                format!(" AND {} = $2", tenant.plain),
+                quote::quote!(.bind(#value))
+            )
+        },
+        None => (
+            quote::quote!(),
+            "",
+            quote::quote!(),
+            quote::quote!(new()),
+            quote::quote!((id,)),
+            quote::quote!(),
+            String::new(),
+            quote::quote!()
+        )
+    };
+    let tenant_take = match table.tenant() {
+        Some(_) => quote::quote!(let tenant = tenant.into_inner();),
+        None => quote::quote!(),
+    };
+    let list_route = tenant_route.to_string();
+    let record_route = format!("{}/{{id}}", tenant_route);
+    let (create_arg, created) = match table.tenant() {
+        Some(_) => (
+            quote::quote!(#tenant_arg,),
+            quote::quote! {
+                #tenant_take
+                let mut record = form.into_inner();
+                #tenant_stamp
+
+                record.insert().await
+            }
+        ),
+        None => (quote::quote!(), quote::quote!(form.into_inner().insert().await))
+    };
+    let admin_sorts = table.attributed().map(|c| c.api.as_str()).collect::<Vec<&str>>().join(", ");
+    let delete_sql = format!("DELETE FROM {} WHERE id = $1{}", table_name, tenant_sql);
+    let deleted = derive_traced(table.execution, quote::quote!(#node #turbofish), "delete", quote::quote!(&sql), quote::quote! {
+        sqlx::query(&sql)
+            .bind(id.clone())
+            #tenant_bind
+            .execute(database::writer())
+    });
 
-            #(
-                pub const #all_const_names: &'static str = #all_tabled;
-            )*
-        }
+    quote::quote!{
+        pub mod admin {
+            use actix_web::{web, HttpResponse};
+
+            use super::#node;
+            #generic_scope
+
+            /// Paging inputs of `list`, read from the `page`, `perPage`, `sort` and `order` query parameters.
+            #[derive(Debug, Clone, Default, derives::Pagination)]
+            #[pagination(sorts = #admin_sorts)]
+            pub struct Paging {
+                pub page: i64,
+                pub per_page: i64,
+                pub sort: String,
+                pub order: String,
+            }
 
-        pub mod parsers {
-            use nulls::Null;
-            use sqlx::{Result, Row, postgres::PgRow};
+            pub async fn list #impl_generics (#create_arg paging: Paging) -> #responder::Result<HttpResponse> #where_clause {
+                #tenant_take
+                let sort = super::order::Sort::from_parts(&paging.sort, &paging.order)
+                    .ok_or_else(|| #responder::to(format!("Unknown sort `{}`", paging.sort)))?;
+                let (records, total) = super::query::Builder #turbofish::#tenant_new
+                    .fetch_page(sort.to_sql(), paging.limit(), paging.offset())
+                    .await?;
+
+                Ok(HttpResponse::Ok().json(serde_json::json!({
+                    "code": 200,
+                    "data": records,
+                    "page": paging.page,
+                    "perPage": paging.per_page,
+                    "total": total
+                })))
+            }
 
-            use crate::#node;
+            pub async fn get #impl_generics (path: web::Path<(#tenant_ty #id_ty,)>) -> #responder::Result<HttpResponse> #where_clause {
+                let #tenant_unpack = path.into_inner();
+                let records = super::query::Builder #turbofish::#tenant_new
+                    .id_eq(id)
+                    .fetch_all()
+                    .await?;
+
+                match records.into_iter().next() {
+                    Some(record) => Ok(HttpResponse::Ok().json(serde_json::json!({
+                        "code": 200,
+                        "data": record
+                    }))),
+                    None => Err(#responder::to(#error))
+                }
+            }
 
-            pub fn parse(row: &PgRow) -> #node {
-                #node::parse(row)
+            pub async fn create #impl_generics (#create_arg form: web::Json<#node #ty_generics>) -> #responder::Result<#node #ty_generics> #where_clause {
+                #created
             }
 
-            pub fn result(row: Result<sqlx::postgres::PgRow>) -> responder::Result<#node> {
-                let result = row.map_err(responder::query)?;
-                let row = parse(&result);
+            pub async fn update #impl_generics (path: web::Path<(#tenant_ty #id_ty,)>, form: web::Json<#node #ty_generics>) -> #responder::Result<#node #ty_generics> #where_clause {
+                let #tenant_unpack = path.into_inner();
+                let mut record = form.into_inner().touch_all();
+                record.id = #record_id;
+                #tenant_stamp
 
-                match !row.is_empty() {
-                    true => Ok(row),
-                    false => Err(responder::to(#error))
-                }
+                record.update().await
             }
 
-            pub fn relational(row: &PgRow) -> Null<#node> {
-                let row = parse(row);
+            pub async fn delete #impl_generics (path: web::Path<(#tenant_ty #id_ty,)>) -> #responder::Result<HttpResponse> #where_clause {
+                let #tenant_unpack = path.into_inner();
+                let sql = #delete_sql.to_string();
+                #commenter
 
-                match row.is_empty() {
-                    true => nulls::undefined(),
-                    false => nulls::new(row)
+                let result = #deleted.map_err(#responder::query)?;
+
+                match result.rows_affected() {
+                    0 => Err(#responder::to(#error)),
+                    _ => Ok(HttpResponse::NoContent().finish())
                 }
             }
+        }
 
-            #(#sub_parser_mod)*
+        impl #impl_generics #node #ty_generics #where_clause {
+            pub fn admin_routes() -> actix_web::Scope {
+                actix_web::web::scope(#scope)
+                    .route(#list_route, actix_web::web::get().to(admin::list #turbofish))
+                    .route(#list_route, actix_web::web::post().to(admin::create #turbofish))
+                    .route(#record_route, actix_web::web::get().to(admin::get #turbofish))
+                    .route(#record_route, actix_web::web::put().to(admin::update #turbofish))
+                    .route(#record_route, actix_web::web::delete().to(admin::delete #turbofish))
+            }
         }
+    }
+}
+
+// Create sqlx::Any implementation, columns of types the Any driver
+// cannot carry are left out of every statement
+fn derive_any(table: &Table) -> TS2 {
+    let Table { node, table_name, responder, nulls, error, unchanged, turbofish, generic_scope, impl_generics, ty_generics, where_clause, .. } = table;
+
+    let all_any_fields = table.attributed().filter(|c| c.is_any).map(|c| &c.field).collect::<Vec<&Ident>>();
+    let all_any_decoders = table.attributed().filter(|c| c.is_any).map(|c| c.decoder(&c.renamed, nulls));
+    let all_any_aliased_str = table.attributed()
+        .filter(|c| c.is_any)
+        .map(|c| c.aliased.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    let all_any_insert_undefined = table.inserted()
+        .filter(|c| c.is_any)
+        .map(|c| {
+            let field = &c.field;
+
+            c.kind.undefined(quote::quote!(record.#field))
+        })
+        .collect::<Vec<TS2>>();
+    let all_any_insert_columns = table.inserted().filter(|c| c.is_any).map(|c| c.plain.as_str());
+    let all_any_insert_binds = table.inserted().filter(|c| c.is_any).map(|c| c.encoder(quote::quote!(record)));
+    let all_insert_timestamps = table.insert_timestamps();
+
+    let all_any_update_undefined = table.updated()
+        .filter(|c| c.is_any)
+        .map(|c| table.untouched(c, quote::quote!(record)))
+        .collect::<Vec<TS2>>();
+    let all_any_update_columns = table.updated().filter(|c| c.is_any).map(|c| c.plain.as_str());
+    let all_any_update_binds = table.updated().filter(|c| c.is_any).map(|c| c.encoder(quote::quote!(record)));
+    let all_any_update_timestamps = table.update_timestamps()
+        .iter()
+        .map(|t| t.replace("now()", "CURRENT_TIMESTAMP"))
+        .collect::<Vec<_>>();
+
+    let select = format!("SELECT {} FROM {}", all_any_aliased_str, table_name);
+
+    // Tenant scoped reads take the tenant, bound the way updates bind it
+    let (any_tenant_param, any_tenant_sql, any_tenant_bind, tenant_any_condition, tenant_any_bind) = match table.tenant() {
+        Some(tenant) => {
+            let tenant_ty = &tenant.inner_ty;
+            let condition = format!(" WHERE {} = {{}}", tenant.plain);
+            let any_condition = format!(" AND {} = {{}}", tenant.plain);
+            let value = derive_encoder(quote::quote!(tenant.clone()), tenant.codec, false);
+            let any_bind = tenant.encoder(quote::quote!(record));
+
+            (
+                quote::quote!(, tenant: impl Into<#tenant_ty>),
+                quote::quote! {
+                    let tenant = Into::<#tenant_ty>::into(tenant);
+                    let sql = format!("{}{}", sql, format!(#condition, placeholder(pool, 1)));
+                },
+                quote::quote!(.bind(#value)),
+                quote::quote! {
+                    index += 1;
+                    let sql = format!("{}{}", sql, format!(#any_condition, placeholder(pool, index)));
+                },
+                quote::quote! {
+                    let query = query.bind(#any_bind);
+                }
+            )
+        },
+        None => (quote::quote!(), quote::quote!(), quote::quote!(), quote::quote!(), quote::quote!())
+    };
+    let any_rows = derive_traced(table.execution, quote::quote!(#node #turbofish), "select", quote::quote!(&sql), quote::quote! {
+        sqlx::query(&sql)#any_tenant_bind.fetch_all(pool)
     });
+    let any_inserted = derive_traced(table.execution, quote::quote!(#node #turbofish), "insert", quote::quote!(&sql), quote::quote!(query().execute(pool)));
+    let any_updated = derive_traced(table.execution, quote::quote!(#node #turbofish), "update", quote::quote!(&sql), quote::quote!(query().execute(pool)));
 
+    quote::quote!{
+        pub mod any {
+            use super::#node;
+            #generic_scope
 
-    // Create Node Related implementations
-    //____________________________________________________________
-    token.extend(quote::quote!{
-        impl #node {
-            pub fn is_empty(&self) -> bool {
-                *self == Self::default()
-            }
+            pub const ALL: &'static str = #all_any_aliased_str;
 
-            pub fn to<T>(&self) -> T
-            where
-                T: From<Self>
-            {
-                T::from(self.clone())
+            pub fn placeholder(pool: &sqlx::AnyPool, index: usize) -> String {
+                match pool.connect_options().database_url.scheme() {
+                    "postgres" | "postgresql" => format!("${}", index),
+                    _ => "?".to_string()
+                }
             }
 
-            pub fn to_json(&self) -> serde_json::Value {
-                serde_json::to_value(self)
-                    .unwrap_or(serde_json::Value::Null)
-            }
+            pub fn parse #impl_generics (row: &sqlx::any::AnyRow) -> #node #ty_generics #where_clause {
+                use sqlx::Row;
 
-            pub fn to_jsonb(&self) -> sqlx::types::Json<Self> {
-                sqlx::types::Json::from(self.clone())
-            }
+                let mut data = #node #turbofish::default();
 
-            #(#all_props)*
+                #(
+                    data.#all_any_fields = #all_any_decoders;
+                )*
 
-            #(#all_setters)*
+                data
+            }
 
-            #(#all_setter_opts)*
+            pub async fn fetch_all #impl_generics (pool: &sqlx::AnyPool #any_tenant_param) -> #responder::Result<Vec<#node #ty_generics>> #where_clause {
+                let sql = #select.to_string();
+                #any_tenant_sql
+                let rows = #any_rows.map_err(#responder::query)?;
 
-            #(#all_clones)*
+                Ok(rows.iter().map(parse #turbofish).collect())
+            }
 
-            #(#all_clears)*
+            pub async fn insert #impl_generics (record: &#node #ty_generics, pool: &sqlx::AnyPool) -> #responder::Result<u64> #where_clause {
+                let mut index = 0;
+                let mut columns = Vec::<String>::new();
+                let mut values = Vec::<String>::new();
 
-            pub fn clear_all(mut self) -> Self {
                 #(
-                    if !self.#all_cleable_fields.is_some() {
-                        self.#all_cleable_fields =  nulls::undefined();
+                    if !#all_any_insert_undefined {
+                        index += 1;
+                        columns.push(#all_any_insert_columns.to_string());
+                        values.push(placeholder(pool, index));
                     }
                 )*
 
-                self
-            }
+                #(
+                    columns.push(#all_insert_timestamps.to_string());
+                    values.push("CURRENT_TIMESTAMP".to_string());
+                )*
 
-            pub fn parse(row: &sqlx::postgres::PgRow) -> Self {
-                use sqlx::Row;
+                let sql = format!("INSERT INTO {} ({}) VALUES ({})", #table_name, columns.join(", "), values.join(", "));
+                let query = || {
+                    let mut query = sqlx::query(&sql);
 
-                let mut data = Self::default();
+                    #(
+                        if !#all_any_insert_undefined {
+                            query = query.bind(#all_any_insert_binds);
+                        }
+                    )*
 
-                #(
-                    data.#all_attributed_fields = nulls::Null::from(row.try_get::<#all_attributed_inner_ty, &str>(#all_attributed_renamed));
-                )*
+                    query
+                };
 
+                let result = #any_inserted.map_err(#responder::query)?;
 
-                data
+                Ok(result.rows_affected())
             }
 
-            #(#sub_parsers)*
-
-            pub async fn update(&self) -> responder::Result<Self> {
+            pub async fn update #impl_generics (record: &#node #ty_generics, pool: &sqlx::AnyPool) -> #responder::Result<u64> #where_clause {
                 let mut index = 0;
                 let mut updates = Vec::<String>::new();  // Specify type explicitly
 
-                 #(
-                    if self.#all_update_fields.is_some() || self.#all_update_fields.is_none() {
+                #(
+                    if !#all_any_update_undefined {
                         index += 1;
-                        updates.push(format!(#all_update_columns, index));
+                        updates.push(format!("{} = {}", #all_any_update_columns, placeholder(pool, index)));
                     }
                 )*
 
+                if updates.is_empty() {
+                    return Err(#responder::to(#unchanged));
+                }
+
+                #(
+                    updates.push(#all_any_update_timestamps.to_string());
+                )*
+
                 index += 1;
-                let sql = format!(r#"
-                    UPDATE {} SET {} WHERE id = ${} RETURNING {}
-                "#, #table_name, updates.join(", "), index, alias::ALL);
+                let sql = format!("UPDATE {} SET {} WHERE id = {}", #table_name, updates.join(", "), placeholder(pool, index));
+                #tenant_any_condition
+                let query = || {
+                    let mut query = sqlx::query(&sql);
+
+                    #(
+                        if !#all_any_update_undefined {
+                            query = query.bind(#all_any_update_binds);
+                        }
+                    )*
+
+                    let query = query.bind(record.id());
+                    #tenant_any_bind
+
+                    query
+                };
+                let result = #any_updated.map_err(#responder::query)?;
+
+                match result.rows_affected() {
+                    0 => Err(#responder::to(#error)),
+                    rows => Ok(rows)
+                }
+            }
+        }
+    }
+}
 
-                let mut query = sqlx::query(&sql);
+// Create FromRow implementation
+fn derive_from_row(table: &Table) -> TS2 {
+    let Table { ast, node, nulls, ty_generics, where_clause, .. } = table;
+
+    let row_generics = derive_utils::derive_merge_generics(&ast.generics, parse_quote!('r));
+    let all_attributed_fields = table.attributed().map(|c| &c.field);
+    let all_plain_decoders = table.attributed().map(|c| c.decoder(&c.plain, nulls));
+
+    quote::quote!{
+        impl #row_generics sqlx::FromRow<'r, sqlx::postgres::PgRow> for #node #ty_generics #where_clause {
+            fn from_row(row: &'r sqlx::postgres::PgRow) -> sqlx::Result<Self> {
+                use sqlx::Row;
+
+                let mut data = Self::default();
 
                 #(
-                    if self.#all_update_fields.is_some() || self.#all_update_fields.is_none() {
-                        query = query.bind(self.#all_update_fields());
-                    }
+                    data.#all_attributed_fields = #all_plain_decoders;
                 )*
 
-                query = query.bind(self.id());
-                parsers::result(query.fetch_one(database::writer()).await)
+                Ok(data)
             }
         }
+    }
+}
 
-        impl actix_web::Responder for #node {
+// Create Responder implementation
+fn derive_responder(table: &Table) -> TS2 {
+    let Table { node, impl_generics, ty_generics, where_clause, .. } = table;
+
+    quote::quote!{
+        impl #impl_generics actix_web::Responder for #node #ty_generics #where_clause {
             type Body = actix_web::body::BoxBody;
 
             fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
@@ -514,9 +3785,285 @@ fn derive(stream: TS2) -> deluxe::Result<TS2> {
                 }))
             }
         }
-    });
+    }
+}
 
+// Words that cannot be used as an alias, either because they are reserved
+// in SQL or because they collide with generated modules
+const RESERVED_ALIASES: &[&str] = &[
+    "all", "alias", "and", "api", "as", "asc", "between", "by", "case", "check", "column",
+    "constraint", "create", "default", "desc", "distinct", "else", "end", "from",
+    "group", "having", "in", "into", "is", "join", "like", "limit", "not", "null",
+    "offset", "on", "or", "order", "parsers", "plain", "prefixed", "renamed", "select", "table",
+    "tabled", "then", "to", "union", "unique", "user", "using", "when", "where", "with",
+];
+
+// Parse and validate the table aliases
+fn derive_aliases(alias: &LitStr) -> deluxe::Result<Vec<String>> {
+    let mut aliases = Vec::<String>::new();
+
+    let values = alias.value()
+        .replace(" ", "")
+        .replace(",", ";")
+        .to_lowercase();
+
+    for value in values.split(';').filter(|s| !s.is_empty()) {
+        let is_identifier = value.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+        if !is_identifier || syn::parse_str::<Ident>(value).is_err() {
+            return Err(syn::Error::new(
+                alias.span(),
+                format!("alias `{}` is not a valid identifier", value)
+            ));
+        }
 
-    // Return the new token
-    Ok(token)
-}
\ No newline at end of file
+        if RESERVED_ALIASES.contains(&value) {
+            return Err(syn::Error::new(
+                alias.span(),
+                format!("alias `{}` is a reserved word", value)
+            ));
+        }
+
+        if aliases.iter().any(|a| a == value) {
+            return Err(syn::Error::new(
+                alias.span(),
+                format!("alias `{}` is declared more than once", value)
+            ));
+        }
+
+        aliases.push(value.to_string());
+    }
+
+    Ok(aliases)
+}
+
+// Blocks that can be toggled through `generate` and `skip_generate`
+const GENERATED_BLOCKS: &[&str] = &[
+    "constants", "order", "parsers", "query", "insert", "update", "from_row", "responder", "stats",
+];
+
+// Parse a comma separated list of generated blocks
+fn derive_generated_blocks(list: &LitStr) -> deluxe::Result<Vec<&'static str>> {
+    let mut blocks = Vec::<&'static str>::new();
+
+    for value in list.value().split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        match GENERATED_BLOCKS.iter().find(|b| **b == value) {
+            Some(block) => blocks.push(block),
+            None => return Err(syn::Error::new(
+                list.span(),
+                format!("unknown generated block `{}`, expected one of: {}", value, GENERATED_BLOCKS.join(", "))
+            ))
+        }
+    }
+
+    Ok(blocks)
+}
+
+// Resolve which blocks should be generated
+fn derive_generates(generate: &Option<LitStr>, skip_generate: &Option<LitStr>) -> deluxe::Result<Vec<&'static str>> {
+    let mut generates = match generate {
+        Some(list) => derive_generated_blocks(list)?,
+        None => GENERATED_BLOCKS.to_vec()
+    };
+
+    if let Some(list) = skip_generate {
+        let skipped = derive_generated_blocks(list)?;
+        generates.retain(|b| !skipped.contains(b));
+    }
+
+    // Insert and update return rows through the generated parsers
+    for block in ["insert", "update"] {
+        if generates.contains(&block) && !generates.contains(&"parsers") {
+            let span = generate.as_ref()
+                .or(skip_generate.as_ref())
+                .map(|l| l.span())
+                .unwrap_or_else(proc_macro2::Span::call_site);
+
+            return Err(syn::Error::new(span, format!("`{}` requires `parsers` to be generated", block)));
+        }
+    }
+
+    Ok(generates)
+}
+
+// Create the `set_insert_id` setter for the configured id strategy
+fn derive_id_setter(strategy: &Option<LitStr>, kind: &FieldKind, nulls: &syn::Path) -> deluxe::Result<TS2> {
+    let id = derive_wrap(kind, quote::quote!(id.to_string()), nulls);
+    let generator = match strategy {
+        None => return Ok(quote::quote!{
+            pub fn set_insert_id<Size>(mut self, size: Size) -> Self
+            where
+                Size: ToString
+            {
+                let size = size.to_string();
+                let id = self.id().unwrap_or_default();
+
+                if id.is_empty() {
+                    let id = match size.to_lowercase().as_str() {
+                        "sm" => ids::sm(),
+                        "md" => ids::md(),
+                        "lg" => ids::lg(),
+                        _ => ids::max(),
+                    };
+
+                    self.id = #id;
+                }
+
+                self
+            }
+        }),
+        Some(strategy) => match strategy.value().as_str() {
+            "uuid_v7" => quote::quote!(uuid::Uuid::now_v7()),
+            "ulid" => quote::quote!(ulid::Ulid::new()),
+            "nanoid" => quote::quote!(nanoid::nanoid!()),
+            path => match syn::parse_str::<syn::Path>(path) {
+                Ok(path) => quote::quote!(#path()),
+                Err(_) => return Err(syn::Error::new(
+                    strategy.span(),
+                    format!("id `{}` is not a known strategy (uuid_v7, ulid, nanoid) or function path", path)
+                ))
+            }
+        }
+    };
+
+    Ok(quote::quote!{
+        pub fn set_insert_id(mut self) -> Self {
+            let id = self.id().unwrap_or_default();
+
+            if id.is_empty() {
+                let id = #generator;
+                self.id = #id;
+            }
+
+            self
+        }
+    })
+}
+
+// How a column value is decoded from rows and bound to queries
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Codec {
+    Plain,
+    Enum,
+    Json,
+}
+
+// Create the codec of a column from its enum and jsonb flags
+fn derive_codec(field: &Ident, attrs: &ColumnAttrs) -> deluxe::Result<Codec> {
+    match (attrs.is_enum.is_set(), attrs.jsonb.is_set()) {
+        (true, true) => Err(syn::Error::new(
+            field.span(),
+            format!("column `{}` cannot be both enum and jsonb", field)
+        )),
+        (true, false) => Ok(Codec::Enum),
+        (false, true) => Ok(Codec::Json),
+        (false, false) => Ok(Codec::Plain)
+    }
+}
+
+// Create the column name, following serde renames of the field unless decoupled with #[column(rename)]
+fn derive_column(ast: &DeriveInput, field: &Ident, attrs: &ColumnAttrs) -> String {
+    match &attrs.rename {
+        Some(rename) => rename.value(),
+        None => derive_utils::derive_snake_case(derive_utils::derive_serde_rename(ast, field)
+            .unwrap_or(field.to_string()))
+    }
+}
+
+// How generated database calls are awaited
+#[derive(Clone, Copy)]
+struct Execution {
+    instrumented: bool,
+    guarded: bool,
+}
+
+// Create the awaited database call, retried through `guard_query` of `path` when guarded
+// and wrapped by its `trace_query` when instrumented
+fn derive_traced(execution: Execution, path: TS2, kind: &str, sql: TS2, call: TS2) -> TS2 {
+    match execution {
+        Execution { guarded: true, .. } => quote::quote! { #path::guard_query(#kind, #sql, || #call).await },
+        Execution { instrumented: true, .. } => quote::quote! { #path::trace_query(#kind, #sql, #call).await },
+        _ => quote::quote! { #call.await },
+    }
+}
+
+// Create the expression reading a column from `row` into a Null or Option field
+fn derive_decoder(kind: &FieldKind, codec: Codec, parse_with: Option<&syn::Path>, column: &str, nulls: &syn::Path) -> TS2 {
+    derive_column_decoder(kind, codec, parse_with, quote::quote!(#column), nulls)
+}
+
+// Create the expression decoding a column whose name is only known at runtime,
+// handing the raw value to `parse_with` when the field has its own decoder
+fn derive_column_decoder(kind: &FieldKind, codec: Codec, parse_with: Option<&syn::Path>, column: TS2, nulls: &syn::Path) -> TS2 {
+    let ty = kind.inner();
+    let decoded = match (parse_with, codec) {
+        (Some(path), _) => quote::quote! {
+            #nulls::Null::<#ty>::from(row.try_get_raw(#column)
+                .map_err(sqlx::error::BoxDynError::from)
+                .and_then(#path))
+        },
+        (None, Codec::Plain | Codec::Json) => quote::quote! {
+            #nulls::Null::from(row.try_get::<#ty, _>(#column))
+        },
+        (None, Codec::Enum) => quote::quote! {
+            #nulls::Null::from(row.try_get::<String, _>(#column).map(<#ty>::from))
+        },
+    };
+
+    match kind {
+        FieldKind::Option(_) => quote::quote! { #decoded.take() },
+        _ => decoded,
+    }
+}
+
+// Create the expression wrapping `value` the way the field stores it
+fn derive_wrap(kind: &FieldKind, value: TS2, nulls: &syn::Path) -> TS2 {
+    match kind {
+        FieldKind::Null(_) => quote::quote! { #nulls::new(#value) },
+        FieldKind::Option(_) => quote::quote! { Some(#value) },
+        _ => value,
+    }
+}
+
+// Create the expression reading a field as an Option, None when unset or NULL
+fn derive_take(kind: &FieldKind, field: TS2) -> TS2 {
+    match kind {
+        FieldKind::Null(_) => quote::quote! { #field.clone().take() },
+        FieldKind::Option(_) => quote::quote! { #field.clone() },
+        _ => quote::quote! { Some(#field.clone()) },
+    }
+}
+
+// Create the value of an unset field
+fn derive_unset(kind: &FieldKind, nulls: &syn::Path) -> TS2 {
+    match kind {
+        FieldKind::Option(_) => quote::quote! { None },
+        _ => quote::quote! { #nulls::undefined() },
+    }
+}
+
+// Create the expression rendering a field of `record` as COPY text, None being NULL
+fn derive_copy_value(field: &Ident, kind: &FieldKind, codec: Codec, is_bytes: bool) -> TS2 {
+    let value = derive_take(kind, quote::quote!(record.#field));
+
+    match (codec, is_bytes) {
+        (Codec::Enum, _) => quote::quote! { #value.map(|v| v.to_string()) },
+        (Codec::Json, _) => quote::quote! { #value.map(|v| serde_json::to_string(&v).unwrap_or_default()) },
+        (Codec::Plain, true) => quote::quote! {
+            #value.map(|v| v.iter().fold(String::from("\\x"), |hex, b| hex + &format!("{:02x}", b)))
+        },
+        (Codec::Plain, false) => quote::quote! { #value.and_then(|v| derives::copy::text(serde_json::to_value(v).unwrap_or_default())) },
+    }
+}
+
+// Create the expression binding `value`, which is an Option when `is_option` is set
+fn derive_encoder(value: TS2, codec: Codec, is_option: bool) -> TS2 {
+    match (codec, is_option) {
+        (Codec::Plain, _) => value,
+        (Codec::Enum, true) => quote::quote! { (#value).map(|v| v.to_string()) },
+        (Codec::Enum, false) => quote::quote! { (#value).to_string() },
+        (Codec::Json, true) => quote::quote! { (#value).map(sqlx::types::Json) },
+        (Codec::Json, false) => quote::quote! { sqlx::types::Json(#value) },
+    }
+}
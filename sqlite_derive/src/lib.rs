@@ -0,0 +1,23 @@
+use proc_macro::TokenStream as TS1;
+use derive_utils::dialect::Dialect;
+
+// The Sqlite dialect, identifiers are quoted with double quotes and writes return the row
+const SQLITE: Dialect = Dialect {
+    name: "Sqlite",
+    version: env!("CARGO_PKG_VERSION"),
+    quote: '"',
+    placeholder: "?",
+    now: "CURRENT_TIMESTAMP",
+    row: "sqlx::sqlite::SqliteRow",
+    pool: "sqlx::SqlitePool",
+    returning: true,
+    actix: cfg!(feature = "actix"),
+};
+
+// Start of derive and field attribute derives
+#[proc_macro_derive(Sqlite, attributes(table, column))]
+pub fn main(stream: TS1) -> TS1 {
+    derive_utils::dialect::derive_sql_model(stream.into(), &SQLITE)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -3,13 +3,220 @@ pub use enums_derive::Enums;
 pub use form_derive::Form;
 pub use is_empty_derive::IsEmpty;
 pub use jsonb_derive::Jsonb;
-pub use postgresql_derive::PostgreSQL;
+pub use mysql_derive::MySQL;
+pub use pagination_derive::Pagination;
+pub use postgresql_derive::{Model, PostgreSQL};
+pub use sqlite_derive::Sqlite;
 
 pub trait Encryption {}
 pub trait Enums {}
 pub trait Form {}
 pub trait IsEmpty {}
 pub trait Jsonb {}
+pub trait Model {}
+pub trait MySQL {}
+pub trait Pagination {}
 pub trait PostgreSQL {}
+pub trait Sqlite {}
 
-pub use derive_utils::Pagination;
\ No newline at end of file
+/// Converts a value into any type that implements `From<Self>`.
+///
+/// Implemented by the `PostgreSQL`, `Model`, `MySQL`, `Sqlite`, `Form` and `Encryption`
+/// derives for the types they expand, `Model` implementing it once for its composed
+/// derives. Callers bring it into scope with `use derives::To` or the prelude.
+pub trait To: Clone {
+    /// Converts a copy of the current instance into `Target`.
+    fn to<Target>(&self) -> Target
+    where
+        Target: From<Self>
+    {
+        Target::from(self.clone())
+    }
+}
+
+/// Observes every query generated by a `#[table(metrics = "...")]` model.
+///
+/// Called once each statement completes, whether it succeeded or not, so counters
+/// and histograms can wrap derive-generated data access.
+pub trait QueryObserver {
+    /// Records a completed statement.
+    ///
+    /// # Arguments
+    /// - `table`: The table the statement ran against.
+    /// - `operation`: The statement kind, e.g. `select`, `insert`, `update` or `delete`.
+    /// - `elapsed`: How long the statement took.
+    fn observe(table: &'static str, operation: &'static str, elapsed: std::time::Duration);
+}
+
+/// A cipher instance bound to one key, used by `Encryption` models to rotate keys.
+///
+/// Passed as the old and new keys to the generated `re_encrypt` and `re_encrypt_key`,
+/// so scheduled rotation can run over every model without bespoke code.
+pub trait Cipher {
+    /// Encrypts a plaintext value with this cipher's key.
+    fn encrypt(&self, plaintext: &str) -> String;
+
+    /// Decrypts a ciphertext value produced with this cipher's key.
+    fn decrypt(&self, ciphertext: &str) -> String;
+}
+
+pub use derive_utils::Paginated;
+
+/// The fields of a `PostgreSQL` model changed through its setters since it was loaded.
+///
+/// Declared on a model as an unattributed `touched` field, usually with `#[serde(skip)]`,
+/// setters record the fields they change and `update()` only writes those columns. Copies made
+/// through `mutate` or from a form touch the fields they set, while deserialized records, which
+/// start untouched, call `touch_all()` to mark every field holding a value. Records always
+/// compare equal, so tracking never makes two otherwise equal models differ.
+#[derive(Debug, Clone, Default)]
+pub struct Touched(std::collections::BTreeSet<&'static str>);
+
+impl Touched {
+    /// Records a field as changed.
+    pub fn touch(&mut self, field: &'static str) {
+        self.0.insert(field);
+    }
+
+    /// Checks whether a field was changed.
+    pub fn contains(&self, field: &str) -> bool {
+        self.0.contains(field)
+    }
+
+    /// Lists the changed fields in name order.
+    pub fn fields(&self) -> Vec<&'static str> {
+        self.0.iter().copied().collect()
+    }
+
+    /// Forgets every change, e.g. once the record was saved.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl PartialEq for Touched {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// Encodes records for `COPY ... FROM STDIN WITH (FORMAT csv)`, as streamed by `copy_in`.
+pub mod copy {
+    /// Converts a serialized value into its COPY text.
+    ///
+    /// Scalars are written as text, arrays as postgres array literals with every element quoted.
+    ///
+    /// # Returns
+    /// - `None` for NULL, the text otherwise.
+    ///
+    /// # Example
+    /// ```rust
+    /// use derives::copy::text;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(text(json!(null)), None);
+    /// assert_eq!(text(json!(42)).unwrap(), "42");
+    /// assert_eq!(text(json!(["a,b", "say \"hi\"", "back\\slash", null])).unwrap(), r#"{"a,b","say \"hi\"","back\\slash",NULL}"#);
+    /// assert_eq!(text(json!([[1, 2], []])).unwrap(), r#"{{"1","2"},{}}"#);
+    /// ```
+    pub fn text(value: serde_json::Value) -> Option<String> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s),
+            serde_json::Value::Array(values) => Some(format!(
+                "{{{}}}",
+                values.into_iter().map(element).collect::<Vec<String>>().join(",")
+            )),
+            value => Some(value.to_string()),
+        }
+    }
+
+    // Quotes an array element, nested arrays stay bare literals
+    fn element(value: serde_json::Value) -> String {
+        match value {
+            serde_json::Value::Array(_) => text(value).unwrap_or_default(),
+            value => match text(value) {
+                Some(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+                None => "NULL".to_string(),
+            }
+        }
+    }
+
+    /// Joins the values of one record into a CSV line.
+    ///
+    /// Unquoted empty fields are NULL, quoted ones are empty strings.
+    ///
+    /// # Example
+    /// ```rust
+    /// use derives::copy::record;
+    ///
+    /// let line = record(vec![Some("a\"b".to_string()), None, Some(String::new()), Some("x,\ny".to_string())]);
+    /// assert_eq!(line, "\"a\"\"b\",,\"\",\"x,\ny\"\n");
+    /// ```
+    pub fn record(values: Vec<Option<String>>) -> String {
+        let mut line = values.into_iter()
+            .map(|value| match value {
+                Some(s) => format!("\"{}\"", s.replace('"', "\"\"")),
+                None => String::new(),
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+
+        line.push('\n');
+        line
+    }
+}
+
+/// The version of the generator, embedded by every derive into the types it expands.
+///
+/// Compare against the hidden `__{DERIVE}_GENERATOR_VERSION` constants of generated types,
+/// e.g. `User::__POSTGRESQL_GENERATOR_VERSION`, to check services were built alike.
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Lists the optional features this crate was built with.
+///
+/// # Returns
+/// The names of the active features, matching the hidden `__{DERIVE}_CAPABILITIES` constants.
+pub fn capabilities() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "actix")]
+        "actix",
+        #[cfg(feature = "admin")]
+        "admin",
+        #[cfg(feature = "audit")]
+        "audit",
+        #[cfg(feature = "sqlcommenter")]
+        "sqlcommenter",
+        #[cfg(feature = "tracing")]
+        "tracing",
+        #[cfg(feature = "utoipa")]
+        "utoipa",
+    ]
+}
+/// Re-exports every derive along with the traits its generated code expects.
+///
+/// Derives and their marker traits share names, so a single glob brings both in.
+///
+/// `Null` is not re-exported: the `nulls` crate is not published to crates.io, so it
+/// cannot be declared here even as an optional dependency without breaking resolution.
+/// Import it next to the prelude, or point `#[table(nulls = "...")]` at its path.
+///
+/// Generated code also names the runtime crates it calls into, which the deriving crate
+/// declares itself:
+/// - `serde`, `serde_json`, `sqlx`, `nulls` and `responder` for models, forms and encryption.
+/// - `database` for the pools of `PostgreSQL` and `Model`.
+/// - `chrono` for timestamp columns, `ids` or `uuid` for generated ids.
+/// - `tokio` for `#[table(healthcheck)]` and `#[table(timeout_ms)]`.
+/// - `ciphers`, `argon2`, `zeroize` and `title_case` for the matching `Encryption` options.
+/// - `actix-web`, `tracing`, `opentelemetry` and `utoipa` for the `actix`, `tracing`,
+///   `sqlcommenter` and `utoipa` features.
+///
+/// The README lists which derive or attribute needs each of them.
+///
+/// ```rust
+/// use derives::prelude::*;
+/// ```
+pub mod prelude {
+    pub use crate::{Encryption, Enums, Form, IsEmpty, Jsonb, Model, MySQL, Pagination, PostgreSQL, Sqlite};
+    pub use crate::{Cipher, Paginated, QueryObserver, To, Touched};
+}